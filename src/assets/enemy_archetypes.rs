@@ -0,0 +1,136 @@
+use std::ops::RangeInclusive;
+
+use serde::Deserialize;
+
+// Gegnertyp als JSON-Asset (assets/enemies/*.enemy.json)
+#[derive(Deserialize, Debug, Clone, bevy::asset::Asset, bevy::reflect::TypePath)]
+pub struct EnemyArchetype {
+    pub name: String,
+    pub weight: f32,     // Spawn-Gewichtung
+    pub size: [f32; 3],  // Kantenlängen des Würfels
+    pub color: [f32; 3], // sRGB
+    pub health: (f32, f32),
+    pub damage: (f32, f32),
+    pub physics: EnemyPhysics,
+    pub movement: EnemyMovementSettings,
+    pub weapon: EnemyWeapon,
+    pub behavior: EnemyBehavior,
+    #[serde(default)]
+    pub perception: EnemyPerceptionSettings,
+    #[serde(default)]
+    pub ai: EnemyAiSettings,
+}
+
+impl EnemyArchetype {
+    pub fn health_range(&self) -> RangeInclusive<f32> {
+        normalized_range(self.health)
+    }
+
+    pub fn damage_range(&self) -> RangeInclusive<f32> {
+        normalized_range(self.damage)
+    }
+}
+
+// Vertauschte oder negative Werte aus dem JSON tolerieren
+fn normalized_range((a, b): (f32, f32)) -> RangeInclusive<f32> {
+    a.min(b).max(0.0)..=a.max(b).max(0.0)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyPhysics {
+    pub density: f32,
+    pub friction: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyMovementSettings {
+    pub move_force: f32,
+    pub max_speed: f32,
+    pub max_turn_speed: f32,
+    pub turn_torque: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EnemyWeapon {
+    None,
+    // Schaden bei Berührung des Spielers
+    Melee {
+        cooldown: f32,
+    },
+    Bullet {
+        interval: f32,
+        bullet_speed: f32,
+        recoil: f32,
+        // 0.0 = kein Vorhalt und große Streuung, 1.0 = perfekter Vorhalt
+        #[serde(default = "default_accuracy")]
+        accuracy: f32,
+        // Schüsse bis zum Nachladen
+        #[serde(default = "default_magazine")]
+        magazine: u32,
+        #[serde(default = "default_reload_secs")]
+        reload_secs: f32,
+    },
+}
+
+fn default_accuracy() -> f32 {
+    0.7
+}
+
+fn default_magazine() -> u32 {
+    3
+}
+
+fn default_reload_secs() -> f32 {
+    3.0
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct EnemyBehavior {
+    // Direkt auf den Spieler zu, ohne Zufallsabweichung
+    pub charge: bool,
+    // Wird bei Berührung des Spielers zur PendingExplosion
+    pub explode_on_contact: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct EnemyPerceptionSettings {
+    pub view_distance: f32,
+    pub view_angle: f32,  // halber Öffnungswinkel in Grad
+    pub memory_secs: f32, // so lange bleibt die letzte bekannte Position gültig
+}
+
+// Schwellwerte für die Zustände der Gegner-KI (siehe game::enemy_ai)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct EnemyAiSettings {
+    pub idle_distance: f32,
+    pub engage_distance: f32, // ab hier wird gekreist statt verfolgt
+    pub flee_health: f32,     // Anteil der Max-Health
+    pub safe_distance: f32,
+}
+
+impl Default for EnemyAiSettings {
+    fn default() -> Self {
+        EnemyAiSettings {
+            idle_distance: 80.0,
+            engage_distance: 15.0,
+            flee_health: 0.3,
+            safe_distance: 25.0,
+        }
+    }
+}
+
+impl Default for EnemyPerceptionSettings {
+    fn default() -> Self {
+        EnemyPerceptionSettings {
+            view_distance: 60.0,
+            view_angle: 70.0,
+            memory_secs: 8.0,
+        }
+    }
+}
@@ -1,27 +1,92 @@
-use bevy::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
 
-mod levels {
-    pub struct Level {
-        pub name: String,
-        pub enemy_count: u32,
-        pub player_start_position: (f32, f32),
-    }
+// Ein Level wird als JSON-Asset geladen (assets/levels/*.level.json)
+#[derive(Deserialize, Debug, Clone, bevy::asset::Asset, bevy::reflect::TypePath)]
+pub struct Level {
+    pub name: String,
+    pub player_start_position: (f32, f32), // (x, z)
+    pub enemy_budget: EnemyBudget,
+    pub waves: WaveSettings,
+    pub forest: ForestSettings,
+    pub ground: GroundSettings,
+    pub walls: WallSettings,
+    // Pfad zum nächsten Level, None = letztes Level
+    pub next_level: Option<String>,
+}
 
-    impl Level {
-        pub fn new(name: &str, enemy_count: u32, player_start_position: (f32, f32)) -> Self {
-            Level {
-                name: name.to_string(),
-                enemy_count,
-                player_start_position,
-            }
-        }
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyBudget {
+    pub max_alive: usize, // Maximal gleichzeitig lebende Gegner
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WaveSettings {
+    pub break_secs: f32, // Pause zwischen zwei Wellen
+    pub difficulty_per_wave: DifficultyScaling,
+    // Nach der letzten Welle geht es ins nächste Level (oder endlos weiter)
+    pub definitions: Vec<WaveDefinition>,
+}
+
+// Zuwachs pro Welle, z.B. 0.1 = +10% je Welle
+#[derive(Deserialize, Debug, Clone)]
+pub struct DifficultyScaling {
+    pub damage: f32,
+    pub max_speed: f32,
+    pub shoot_rate: f32,
+    #[serde(default)]
+    pub accuracy: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WaveDefinition {
+    pub enemy_count: u32,        // Spawn-Budget der Welle
+    pub spawn_delay: (f32, f32), // Zufälliger Abstand zwischen zwei Spawns
+    // Gewichte je Gegnertyp (Name), leer = Standardgewichte der Typen
+    #[serde(default)]
+    pub archetypes: HashMap<String, f32>,
+}
+
+impl WaveDefinition {
+    // Vertauschte oder negative Werte aus dem JSON tolerieren
+    pub fn spawn_delay_range(&self) -> std::ops::RangeInclusive<f32> {
+        let (a, b) = self.spawn_delay;
+        a.min(b).max(0.0)..=a.max(b).max(0.0)
     }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ForestSettings {
+    pub seed: u32,
+    pub tree_count: usize,
+    pub min_distance: f32,       // Mindestabstand zwischen Bäumen
+    pub noise_scale: f64,        // Größe der Wäldchen
+    pub noise_threshold: f64,    // Ab welchem Noise-Wert Wald wächst
+    pub lonely_tree_chance: f64, // Einzelne Bäume außerhalb des Waldes
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GroundSettings {
+    pub size: f32, // Kantenlänge, gleichzeitig die Levelgrenze
+    pub texture: String,
+    pub normal_map: String,
+    pub roughness_map: String,
+    pub texture_tile_size: f32, // Meter pro Textur-Kachel
+    pub perceptual_roughness: f32,
+    pub reflectance: f32,
+    pub friction: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct WallSettings {
+    pub height: f32,
+    pub thickness: f32,
+    pub color: [f32; 3],
+}
 
-    pub fn load_levels() -> Vec<Level> {
-        vec![
-            Level::new("Level 1", 5, (0.0, 0.0)),
-            Level::new("Level 2", 10, (1.0, 1.0)),
-            Level::new("Level 3", 15, (2.0, 2.0)),
-        ]
+impl Level {
+    // Halbe Kantenlänge: alles innerhalb von ±bounds liegt im Level
+    pub fn bounds(&self) -> f32 {
+        self.ground.size / 2.0
     }
 }
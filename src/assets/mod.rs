@@ -1 +1,12 @@
+pub mod enemy_archetypes;
 pub mod levels;
+pub mod particle_effects;
+use bevy::prelude::*;
+
+// Wird Done, sobald Level- und Baum-Assets geladen sind; bei Levelwechsel wieder Loading
+#[derive(States, Debug, Clone, Eq, PartialEq, Hash, Default)]
+pub enum AssetLoadingState {
+    #[default]
+    Loading,
+    Done,
+}
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+// Partikeleffekt als JSON-Asset (assets/particles/*.particles.json)
+#[derive(Deserialize, Debug, Clone, bevy::asset::Asset, bevy::reflect::TypePath)]
+pub struct ParticleEffect {
+    pub emitters: Vec<ParticleEmitter>,
+}
+
+// Alle Bereiche (min, max) werden pro Partikel gleichverteilt ausgewürfelt
+#[derive(Deserialize, Debug, Clone)]
+pub struct ParticleEmitter {
+    pub count: (u32, u32),
+    pub visual: ParticleVisual,
+    // Fester Versatz des Startpunkts
+    #[serde(default)]
+    pub offset: [f32; 3],
+    // Halbe Kantenlängen der Startbox, wird mit der Streuung des Auslösers multipliziert
+    #[serde(default)]
+    pub spread: [f32; 3],
+    // Winkel zur Hochachse in Grad: 0 = senkrecht nach oben, 90 = waagerecht
+    pub polar_angle: (f32, f32),
+    pub speed: (f32, f32),
+    pub lifetime: (f32, f32),
+    #[serde(default)]
+    pub gravity: f32,
+    pub size: (f32, f32),
+    // Faktor auf die Größe am Anfang und am Ende der Lebenszeit
+    #[serde(default = "default_size_over_life")]
+    pub size_over_life: (f32, f32),
+    // Maximale Drehgeschwindigkeit (rad/s) um eine zufällige Achse
+    #[serde(default)]
+    pub rotation_speed: f32,
+    #[serde(default)]
+    pub random_rotation: bool,
+}
+
+fn default_size_over_life() -> (f32, f32) {
+    (1.0, 1.0)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParticleVisual {
+    // Kugel mit eigenem Material, Farbe wird über die Lebenszeit überblendet
+    Sphere {
+        start_color: [f32; 4], // sRGBA
+        end_color: [f32; 4],
+        // Zufällige Abweichung je Farbkanal
+        #[serde(default)]
+        color_variance: f32,
+        #[serde(default)]
+        perceptual_roughness: Option<f32>,
+    },
+    // Zufällig gewählte glTF-Szene
+    Scenes {
+        paths: Vec<String>,
+    },
+}
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::Rng;
+
+use crate::{AppState, Player};
+
+use super::perception::{Perception, enemy_perception_system};
+
+pub struct AimingPlugin;
+
+impl Plugin for AimingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            enemy_aim_system
+                .after(enemy_perception_system)
+                .run_if(in_state(AppState::Running)),
+        );
+    }
+}
+
+// Maximale Streuung bei Treffsicherheit 0
+const MAX_SPREAD: f32 = 0.15;
+// Iterationen für den Ausgleich der Schwerkraft
+const GRAVITY_ITERATIONS: usize = 4;
+
+// Zielvorgabe für Gegner mit Projektilwaffe
+#[derive(Component, Debug)]
+pub struct EnemyAim {
+    pub accuracy: f32, // 0.0 = schießt daneben, 1.0 = perfekter Vorhalt
+    pub projectile_speed: f32,
+    pub launch_velocity: Option<Vec3>, // None = kein Ziel
+}
+
+impl EnemyAim {
+    pub fn new(accuracy: f32, projectile_speed: f32) -> Self {
+        EnemyAim {
+            accuracy: accuracy.clamp(0.0, 1.0),
+            projectile_speed,
+            launch_velocity: None,
+        }
+    }
+
+    // Horizontale Blickrichtung zum Vorhaltepunkt (für turn_torque)
+    pub fn facing(&self) -> Option<Vec3> {
+        self.launch_velocity
+            .map(|velocity| velocity.with_y(0.0).normalize_or_zero())
+            .filter(|dir| *dir != Vec3::ZERO)
+    }
+
+    // Schussgeschwindigkeit mit zufälliger Streuung je nach Treffsicherheit
+    pub fn shot_velocity(&self, rng: &mut impl Rng) -> Option<Vec3> {
+        let velocity = self.launch_velocity?;
+        let spread = (1.0 - self.accuracy) * MAX_SPREAD;
+        if spread <= 0.0 {
+            return Some(velocity);
+        }
+        let yaw = rng.random_range(-spread..=spread);
+        let pitch = rng.random_range(-spread..=spread) * 0.5;
+        let right = velocity.cross(Vec3::Y).normalize_or(Vec3::X);
+        Some(Quat::from_rotation_y(yaw) * Quat::from_axis_angle(right, pitch) * velocity)
+    }
+}
+
+// Flugzeit bis zum Abfangpunkt bei geradliniger Zielbewegung.
+// Löst |p + v t| = s t; None, wenn das Projektil das Ziel nie einholt.
+pub fn intercept_time(
+    relative_pos: Vec3,
+    target_velocity: Vec3,
+    projectile_speed: f32,
+) -> Option<f32> {
+    let a = target_velocity.length_squared() - projectile_speed * projectile_speed;
+    let b = 2.0 * relative_pos.dot(target_velocity);
+    let c = relative_pos.length_squared();
+
+    if a.abs() < 1e-4 {
+        // Ziel ist (fast) genau so schnell wie das Projektil
+        return (b < 0.0).then(|| -c / b);
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrt = discriminant.sqrt();
+    let t1 = (-b - sqrt) / (2.0 * a);
+    let t2 = (-b + sqrt) / (2.0 * a);
+    [t1, t2]
+        .into_iter()
+        .filter(|t| *t > 0.0)
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+// Abschussgeschwindigkeit, die das bewegte Ziel trotz Schwerkraft trifft.
+// Startet mit der Lösung ohne Schwerkraft und verfeinert die Flugzeit iterativ.
+pub fn lead_velocity(
+    shooter: Vec3,
+    target: Vec3,
+    target_velocity: Vec3,
+    projectile_speed: f32,
+    gravity: Vec3,
+) -> Option<Vec3> {
+    let relative_pos = target - shooter;
+    let mut time = intercept_time(relative_pos, target_velocity, projectile_speed)?;
+
+    for _ in 0..GRAVITY_ITERATIONS {
+        let offset = relative_pos + target_velocity * time - 0.5 * gravity * time * time;
+        time = offset.length() / projectile_speed;
+    }
+    if time <= 0.0 {
+        return None;
+    }
+    let offset = relative_pos + target_velocity * time - 0.5 * gravity * time * time;
+    Some(offset / time)
+}
+
+pub fn enemy_aim_system(
+    player_query: Single<(&Transform, &Velocity), With<Player>>,
+    rapier_config: Query<&RapierConfiguration>,
+    mut enemy_query: Query<(&Transform, &Perception, &mut EnemyAim)>,
+) {
+    let (player_transform, player_velocity) = player_query.into_inner();
+    let gravity = rapier_config
+        .iter()
+        .next()
+        .map_or(Vec3::Y * -9.81, |config| config.gravity);
+
+    for (transform, perception, mut aim) in enemy_query.iter_mut() {
+        if !perception.sees_player {
+            aim.launch_velocity = None;
+            continue;
+        }
+        // Schwache Schützen unterschätzen die Bewegung des Spielers
+        let predicted_velocity = player_velocity.linvel * aim.accuracy;
+        aim.launch_velocity = lead_velocity(
+            transform.translation,
+            player_transform.translation,
+            predicted_velocity,
+            aim.projectile_speed,
+            gravity,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAVITY: Vec3 = Vec3::new(0.0, -9.81, 0.0);
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "erwartet {expected}, war {actual}"
+        );
+    }
+
+    #[test]
+    fn stationary_target() {
+        let time = intercept_time(Vec3::new(6.0, 0.0, 8.0), Vec3::ZERO, 5.0).unwrap();
+        assert_close(time, 2.0);
+    }
+
+    #[test]
+    fn target_moving_towards_and_away() {
+        let towards = intercept_time(Vec3::X * 10.0, Vec3::X * -5.0, 5.0).unwrap();
+        assert_close(towards, 1.0);
+        let away = intercept_time(Vec3::X * 10.0, Vec3::X * 5.0, 10.0).unwrap();
+        assert_close(away, 2.0);
+    }
+
+    #[test]
+    fn target_as_fast_as_projectile() {
+        // a ≈ 0: nur treffbar, wenn das Ziel entgegenkommt
+        let towards = intercept_time(Vec3::X * 10.0, Vec3::X * -10.0, 10.0).unwrap();
+        assert_close(towards, 0.5);
+        assert!(intercept_time(Vec3::X * 10.0, Vec3::X * 10.0, 10.0).is_none());
+    }
+
+    #[test]
+    fn target_too_fast() {
+        // Flieht schneller als das Projektil
+        assert!(intercept_time(Vec3::X * 10.0, Vec3::X * 20.0, 10.0).is_none());
+        // Negative Diskriminante: seitlich zu schnell
+        assert!(intercept_time(Vec3::X * 10.0, Vec3::Z * 20.0, 10.0).is_none());
+    }
+
+    #[test]
+    fn picks_earliest_positive_root() {
+        // Schnelles Ziel kommt entgegen: zwei positive Lösungen, 1/3 und 1
+        let time = intercept_time(Vec3::X * 10.0, Vec3::X * -20.0, 10.0).unwrap();
+        assert_close(time, 1.0 / 3.0);
+    }
+
+    #[test]
+    fn lead_velocity_compensates_gravity() {
+        let shooter = Vec3::new(0.0, 1.0, 0.0);
+        let target = Vec3::new(20.0, 1.0, 5.0);
+        let target_velocity = Vec3::new(0.0, 0.0, 3.0);
+        let speed = 30.0;
+
+        let velocity = lead_velocity(shooter, target, target_velocity, speed, GRAVITY).unwrap();
+        assert!((velocity.length() - speed).abs() < 0.5);
+        assert!(
+            velocity.y > 0.0,
+            "muss gegen die Schwerkraft nach oben zielen"
+        );
+
+        // Flugbahn bis zur Flugzeit nachrechnen (x bewegt sich beim Ziel nicht)
+        let time = (target.x - shooter.x) / velocity.x;
+        let hit = shooter + velocity * time + 0.5 * GRAVITY * time * time;
+        let target_then = target + target_velocity * time;
+        assert!(hit.distance(target_then) < 1e-3);
+    }
+}
@@ -0,0 +1,194 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+pub struct AssetCachePlugin;
+
+impl Plugin for AssetCachePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameAssetCache>()
+            .register_diagnostic(Diagnostic::new(MESH_COUNT))
+            .register_diagnostic(Diagnostic::new(MATERIAL_COUNT))
+            .add_systems(Update, asset_count_diagnostics_system);
+    }
+}
+
+// Anzahl lebender Meshes und Materialien. Bleibt im Spiel annähernd konstant,
+// solange nichts pro Spawn neue Assets anlegt.
+pub const MESH_COUNT: DiagnosticPath = DiagnosticPath::const_new("game/mesh_count");
+pub const MATERIAL_COUNT: DiagnosticPath = DiagnosticPath::const_new("game/material_count");
+
+pub const PLAYER_BULLET_COLOR: Color = Color::srgb(0.2, 0.8, 1.0);
+pub const ENEMY_BULLET_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+
+// Gemeinsame Handles für alles, was oft gespawnt wird. Eigene Kopien
+// (z.B. Treffer-Aufblitzen, Aufladen vor der Explosion) nur wo nötig.
+#[derive(Resource)]
+pub struct GameAssetCache {
+    pub bullet_mesh: Handle<Mesh>,
+    pub player_bullet_material: Handle<StandardMaterial>,
+    pub enemy_bullet_material: Handle<StandardMaterial>,
+    pub unit_sphere: Handle<Mesh>, // Partikel, Größe über die Skalierung
+    pub unit_disc: Handle<Mesh>,   // liegt in der XY-Ebene
+    pub pickup_mesh: Handle<Mesh>,
+    // Erst zur Laufzeit bekannte Größen und Farben (z.B. aus Gegnertypen)
+    cuboids: HashMap<[u32; 3], Handle<Mesh>>,
+    materials: HashMap<String, Handle<StandardMaterial>>,
+}
+
+impl FromWorld for GameAssetCache {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let bullet_mesh = meshes.add(Sphere::new(0.2));
+        let unit_sphere = meshes.add(Sphere::new(1.0));
+        let unit_disc = meshes.add(Circle::new(1.0));
+        let pickup_mesh = meshes.add(Cuboid::new(0.5, 0.5, 0.5));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let player_bullet_material = materials.add(PLAYER_BULLET_COLOR);
+        let enemy_bullet_material = materials.add(ENEMY_BULLET_COLOR);
+
+        GameAssetCache {
+            bullet_mesh,
+            player_bullet_material,
+            enemy_bullet_material,
+            unit_sphere,
+            unit_disc,
+            pickup_mesh,
+            cuboids: HashMap::default(),
+            materials: HashMap::default(),
+        }
+    }
+}
+
+impl GameAssetCache {
+    // Quader-Mesh je Kantenlänge, wird beim ersten Bedarf angelegt
+    pub fn cuboid(&mut self, meshes: &mut Assets<Mesh>, size: Vec3) -> Handle<Mesh> {
+        self.cuboids
+            .entry(size.to_array().map(f32::to_bits))
+            .or_insert_with(|| meshes.add(Cuboid::from_size(size)))
+            .clone()
+    }
+
+    // Einfarbiges Material, geteilt von allen mit derselben Farbe
+    pub fn color_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+        color: Color,
+    ) -> Handle<StandardMaterial> {
+        let key = format!("color:{:?}", color.to_srgba().to_u8_array());
+        self.material(materials, &key, || StandardMaterial::from(color))
+    }
+
+    // Beliebiges Material unter einem eindeutigen Namen
+    pub fn material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+        key: &str,
+        make: impl FnOnce() -> StandardMaterial,
+    ) -> Handle<StandardMaterial> {
+        if let Some(handle) = self.materials.get(key) {
+            return handle.clone();
+        }
+        let handle = materials.add(make());
+        self.materials.insert(key.to_string(), handle.clone());
+        handle
+    }
+}
+
+fn asset_count_diagnostics_system(
+    mut diagnostics: Diagnostics,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<StandardMaterial>>,
+) {
+    diagnostics.add_measurement(&MESH_COUNT, || meshes.len() as f64);
+    diagnostics.add_measurement(&MATERIAL_COUNT, || materials.len() as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{
+        assets::enemy_archetypes::EnemyArchetype,
+        game::{
+            bullet::{Bullet, ProjectilePool, spawn_bullet},
+            enemy::{EnemyScaling, spawn_enemy},
+            health::Faction,
+        },
+    };
+
+    fn archetypes() -> Vec<EnemyArchetype> {
+        [
+            include_str!("../../assets/enemies/grunt.enemy.json"),
+            include_str!("../../assets/enemies/charger.enemy.json"),
+            include_str!("../../assets/enemies/kamikaze.enemy.json"),
+            include_str!("../../assets/enemies/sniper.enemy.json"),
+        ]
+        .into_iter()
+        .map(|json| serde_json::from_str(json).expect("Gegnertyp lässt sich nicht lesen"))
+        .collect()
+    }
+
+    // Je Gegnertyp ein Gegner und pro Seite ein Projektil
+    fn spawn_round(world: &mut World, archetypes: Vec<EnemyArchetype>) {
+        world
+            .run_system_once(
+                move |mut commands: Commands,
+                      mut cache: ResMut<GameAssetCache>,
+                      mut pool: ResMut<ProjectilePool>,
+                      mut meshes: ResMut<Assets<Mesh>>,
+                      mut materials: ResMut<Assets<StandardMaterial>>| {
+                    for archetype in &archetypes {
+                        let enemy = spawn_enemy(
+                            &mut commands,
+                            &mut cache,
+                            &mut meshes,
+                            &mut materials,
+                            archetype,
+                            Vec3::ZERO,
+                            EnemyScaling::default(),
+                        );
+                        for faction in [Faction::Player, Faction::Enemy] {
+                            spawn_bullet(
+                                &mut commands,
+                                &mut pool,
+                                &cache,
+                                Bullet::new(enemy, faction, 1.0),
+                                Vec3::ZERO,
+                                Vec3::X * 30.0,
+                            );
+                        }
+                    }
+                },
+            )
+            .expect("Spawn-System lässt sich nicht ausführen");
+    }
+
+    #[test]
+    fn spawning_does_not_create_assets() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<StandardMaterial>>();
+        world.init_resource::<GameAssetCache>();
+        world.init_resource::<ProjectilePool>();
+
+        // Erste Runde legt die Quader und Farben der Gegnertypen an
+        spawn_round(&mut world, archetypes());
+        let meshes = world.resource::<Assets<Mesh>>().len();
+        let materials = world.resource::<Assets<StandardMaterial>>().len();
+
+        for _ in 0..10 {
+            spawn_round(&mut world, archetypes());
+        }
+        assert_eq!(world.resource::<Assets<Mesh>>().len(), meshes);
+        assert_eq!(
+            world.resource::<Assets<StandardMaterial>>().len(),
+            materials
+        );
+        assert_eq!(world.resource::<ProjectilePool>().stats.active, 11 * 4 * 2);
+    }
+}
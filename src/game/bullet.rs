@@ -1,9 +1,68 @@
-use crate::{AppState, Enemy, Health, Player};
-use bevy::prelude::*;
+use crate::{AppState, Enemy, Health, Player, assets::AssetLoadingState};
+
+use super::{
+    asset_cache::GameAssetCache,
+    health::{DamageKind, Faction},
+};
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
 use bevy_rapier3d::prelude::*;
 
+// Wer geschossen hat und was ein Treffer anrichtet
 #[derive(Debug, Clone, Component)]
-pub struct Bullet;
+pub struct Bullet {
+    pub owner: Option<Entity>,
+    pub faction: Faction,
+    pub damage: f32,
+    pub kind: DamageKind,
+}
+
+impl Bullet {
+    pub fn new(owner: Entity, faction: Faction, damage: f32) -> Self {
+        Bullet {
+            owner: Some(owner),
+            faction,
+            damage,
+            kind: DamageKind::Bullet,
+        }
+    }
+}
+
+// Regeln für Treffer innerhalb derselben Seite
+#[derive(Resource, Clone, Debug)]
+pub struct FriendlyFireSettings {
+    pub self_hit_scale: f32, // abgeprallte eigene Kugeln treffen den Schützen
+    pub enemy_vs_enemy_scale: f32, // Gegnerkugeln gegen andere Gegner
+    pub enemy_kills_detonate: bool, // von Gegnern getötete Gegner explodieren
+}
+
+impl Default for FriendlyFireSettings {
+    fn default() -> Self {
+        FriendlyFireSettings {
+            self_hit_scale: 0.0,
+            enemy_vs_enemy_scale: 0.5,
+            enemy_kills_detonate: false,
+        }
+    }
+}
+
+impl FriendlyFireSettings {
+    // Schadensfaktor eines Treffers; 0.0 = Kugel prallt wirkungslos ab
+    pub fn damage_scale(&self, bullet: &Bullet, target: Entity, target_faction: Faction) -> f32 {
+        if bullet.owner == Some(target) {
+            self.self_hit_scale
+        } else if bullet.faction != target_faction {
+            1.0
+        } else {
+            match target_faction {
+                Faction::Player => self.self_hit_scale,
+                Faction::Enemy => self.enemy_vs_enemy_scale,
+            }
+        }
+    }
+}
 
 #[derive(Resource, Clone)]
 struct BounceSound(Handle<AudioSource>);
@@ -13,31 +72,215 @@ pub struct BulletLifetime {
     pub timer: Timer,
 }
 
+const BULLET_LIFETIME_SECS: f32 = 4.0;
+// Ab dieser Geschwindigkeit (m/s) bekommen Projektile CCD gegen Tunneln
+const CCD_MIN_SPEED: f32 = 20.0;
+pub const DEFAULT_MAX_BOUNCES: u32 = 3;
+
+// Zählt Aufpraller, nach max_bounces wird das Projektil eingesammelt
+#[derive(Component, Debug)]
+pub struct BounceLimit {
+    pub bounces: u32,
+    pub max_bounces: u32,
+}
+
+impl BounceLimit {
+    pub fn new(max_bounces: u32) -> Self {
+        BounceLimit {
+            bounces: 0,
+            max_bounces,
+        }
+    }
+}
+
+// Markiert ein Projektil, das zurück in den Pool soll (statt despawn)
+#[derive(Component)]
+pub struct SpentProjectile;
+
+// Deaktivierte Projektile zum Wiederverwenden
+#[derive(Resource, Default)]
+pub struct ProjectilePool {
+    free: Vec<Entity>,
+    // Erst im nächsten Frame wiederverwendbar, wenn die Deaktivierung sicher angewendet ist
+    pending: Vec<Entity>,
+    pub stats: ProjectileStats,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ProjectileStats {
+    pub created: u64,  // neu gespawnte Entities
+    pub reused: u64,   // aus dem Pool geholt
+    pub recycled: u64, // in den Pool zurückgelegt
+    pub active: usize,
+    pub peak_active: usize,
+}
+
+pub const PROJECTILES_ACTIVE: DiagnosticPath = DiagnosticPath::const_new("game/projectiles_active");
+pub const PROJECTILE_POOL_SIZE: DiagnosticPath =
+    DiagnosticPath::const_new("game/projectile_pool_size");
+
+// Gemeinsamer Spawn für alle Projektile (Gegner und Spieler).
+// Holt wenn möglich ein deaktiviertes Projektil aus dem Pool.
+pub fn spawn_bullet(
+    commands: &mut Commands,
+    pool: &mut ProjectilePool,
+    cache: &GameAssetCache,
+    bullet: Bullet,
+    position: Vec3,
+    velocity: Vec3,
+) -> Entity {
+    let material = match bullet.faction {
+        Faction::Player => cache.player_bullet_material.clone(),
+        Faction::Enemy => cache.enemy_bullet_material.clone(),
+    };
+    // Alles, was sich pro Schuss ändert
+    let state = (
+        bullet,
+        BulletLifetime {
+            timer: Timer::from_seconds(BULLET_LIFETIME_SECS, TimerMode::Once),
+        },
+        BounceLimit::new(DEFAULT_MAX_BOUNCES),
+        MeshMaterial3d(material),
+        Transform::from_translation(position),
+        Visibility::Visible,
+        Velocity::linear(velocity),
+        Ccd {
+            enabled: velocity.length() >= CCD_MIN_SPEED,
+        },
+    );
+
+    pool.stats.active += 1;
+    pool.stats.peak_active = pool.stats.peak_active.max(pool.stats.active);
+
+    if let Some(entity) = pool.free.pop() {
+        pool.stats.reused += 1;
+        commands
+            .entity(entity)
+            .remove::<(RigidBodyDisabled, ColliderDisabled)>()
+            .insert(state);
+        return entity;
+    }
+
+    pool.stats.created += 1;
+    commands
+        .spawn((
+            state,
+            Restitution {
+                coefficient: 1.0,
+                combine_rule: CoefficientCombineRule::Max,
+            },
+            Mesh3d(cache.bullet_mesh.clone()),
+            RigidBody::Dynamic,
+            Collider::ball(0.2),
+            ActiveEvents::COLLISION_EVENTS,
+            ColliderMassProperties::Density(2.0),
+            Friction {
+                coefficient: 0.1, // oder ein Wert nach Geschmack, z.B. 0.5–1.0
+                combine_rule: CoefficientCombineRule::Average,
+            },
+        ))
+        .id()
+}
+
 fn bullet_lifetime_system(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut BulletLifetime)>,
+    mut query: Query<(Entity, &mut BulletLifetime), With<Bullet>>,
 ) {
     for (entity, mut lifetime) in query.iter_mut() {
         lifetime.timer.tick(time.delta());
         if lifetime.timer.finished() {
-            commands.entity(entity).despawn();
+            commands.entity(entity).try_insert(SpentProjectile);
         }
     }
 }
 
+impl ProjectilePool {
+    // Physik und Darstellung abschalten, statt zu despawnen
+    fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands
+            .entity(entity)
+            .remove::<(Bullet, SpentProjectile)>()
+            .insert((
+                RigidBodyDisabled,
+                ColliderDisabled,
+                Visibility::Hidden,
+                Velocity::zero(),
+            ));
+        self.pending.push(entity);
+        self.stats.recycled += 1;
+        self.stats.active = self.stats.active.saturating_sub(1);
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.free.len() + self.pending.len()
+    }
+}
+
+// Läuft vor allen Spawnern, die Befehle aus release sind dann ausgeführt
+fn release_pending_projectiles_system(mut pool: ResMut<ProjectilePool>) {
+    let pool = &mut *pool;
+    pool.free.append(&mut pool.pending);
+}
+
+fn recycle_projectiles_system(
+    mut commands: Commands,
+    mut pool: ResMut<ProjectilePool>,
+    query: Query<Entity, (With<Bullet>, With<SpentProjectile>)>,
+) {
+    for entity in query.iter() {
+        pool.release(&mut commands, entity);
+    }
+}
+
+// Jeder Aufprall zählt, zu oft abgeprallte Projektile werden eingesammelt
+fn bounce_limit_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut bullet_query: Query<&mut BounceLimit, With<Bullet>>,
+) {
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = event else {
+            continue;
+        };
+        for entity in [*e1, *e2] {
+            if let Ok(mut limit) = bullet_query.get_mut(entity) {
+                limit.bounces += 1;
+                if limit.bounces > limit.max_bounces {
+                    commands.entity(entity).try_insert(SpentProjectile);
+                }
+            }
+        }
+    }
+}
+
+fn projectile_diagnostics_system(mut diagnostics: Diagnostics, pool: Res<ProjectilePool>) {
+    diagnostics.add_measurement(&PROJECTILES_ACTIVE, || pool.stats.active as f64);
+    diagnostics.add_measurement(&PROJECTILE_POOL_SIZE, || pool.free_count() as f64);
+}
+
+fn clear_bullets(
+    mut commands: Commands,
+    mut pool: ResMut<ProjectilePool>,
+    query: Query<Entity, With<Bullet>>,
+) {
+    for entity in query.iter() {
+        pool.release(&mut commands, entity);
+    }
+}
+
 fn bounce_sound_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     bounce_sound: Res<BounceSound>,
-    bullet_query: Query<(&Transform, Entity), With<Bullet>>,
+    bullet_query: Query<&Transform, With<Bullet>>,
     player_transform: Single<&Transform, With<Player>>,
 ) {
     let max_distance_from_player = 2.0;
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
             // Prüfe, ob einer der beiden ein Bullet ist und wie weit entfernt
-            let Some((bullet_transform, bullet_entity)) = bullet_query
+            let Some(bullet_transform) = bullet_query
                 .get(*e1)
                 .ok()
                 .or_else(|| bullet_query.get(*e2).ok())
@@ -48,9 +291,11 @@ fn bounce_sound_system(
             if (bullet_transform.translation - player_transform.translation).length()
                 < max_distance_from_player
             {
-                commands.entity(bullet_entity).insert((
+                // Eigene Entity, damit gepoolte Projektile keinen AudioSink behalten
+                commands.spawn((
                     AudioPlayer::new(bounce_sound.0.clone()),
-                    PlaybackSettings::ONCE.with_spatial(true),
+                    PlaybackSettings::DESPAWN.with_spatial(true),
+                    Transform::from_translation(bullet_transform.translation),
                 ));
             }
         }
@@ -66,9 +311,85 @@ pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup).add_systems(
-            Update,
-            (bullet_lifetime_system, bounce_sound_system).run_if(in_state(AppState::Running)),
-        );
+        app.init_resource::<ProjectilePool>()
+            .init_resource::<FriendlyFireSettings>()
+            .register_diagnostic(Diagnostic::new(PROJECTILES_ACTIVE))
+            .register_diagnostic(Diagnostic::new(PROJECTILE_POOL_SIZE))
+            .add_systems(Startup, setup)
+            .add_systems(First, release_pending_projectiles_system)
+            .add_systems(OnExit(AppState::GameOver), clear_bullets)
+            // Levelwechsel: fliegende Projektile zurück in den Pool
+            .add_systems(OnEnter(AssetLoadingState::Loading), clear_bullets)
+            .add_systems(
+                Update,
+                (
+                    (
+                        bullet_lifetime_system,
+                        bounce_sound_system,
+                        bounce_limit_system,
+                    ),
+                    recycle_projectiles_system,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Running)),
+            )
+            .add_systems(Update, projectile_diagnostics_system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn shoot(world: &mut World, release_first: bool) -> Entity {
+        world
+            .run_system_once(
+                move |mut commands: Commands,
+                      mut pool: ResMut<ProjectilePool>,
+                      cache: Res<GameAssetCache>,
+                      query: Query<Entity, With<Bullet>>| {
+                    if release_first {
+                        for entity in query.iter() {
+                            pool.release(&mut commands, entity);
+                        }
+                    }
+                    let owner = commands.spawn_empty().id();
+                    spawn_bullet(
+                        &mut commands,
+                        &mut pool,
+                        &cache,
+                        Bullet::new(owner, Faction::Enemy, 1.0),
+                        Vec3::ZERO,
+                        Vec3::X * 30.0,
+                    )
+                },
+            )
+            .expect("Schuss-System lässt sich nicht ausführen")
+    }
+
+    #[test]
+    fn released_projectile_is_reused_next_frame_only() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<StandardMaterial>>();
+        world.init_resource::<GameAssetCache>();
+        world.init_resource::<ProjectilePool>();
+
+        let first = shoot(&mut world, false);
+        // Gleicher Frame: die Deaktivierung ist noch nicht angewendet
+        let second = shoot(&mut world, true);
+        assert_ne!(first, second);
+        assert_eq!(world.resource::<ProjectilePool>().stats.reused, 0);
+
+        world
+            .run_system_once(release_pending_projectiles_system)
+            .unwrap();
+        let third = shoot(&mut world, false);
+        assert_eq!(third, first);
+        assert!(world.get::<RigidBodyDisabled>(third).is_none());
+        assert!(world.get::<Bullet>(third).is_some());
+        assert_eq!(world.resource::<ProjectilePool>().stats.reused, 1);
     }
 }
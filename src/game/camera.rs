@@ -5,26 +5,43 @@ use bevy::{
     },
     prelude::*,
 };
+use noise::{NoiseFn, Perlin};
 
 use crate::AppState;
 
 const CAMERA_ZOOM_SPEED: f32 = 0.2;
+// Trauma, das pro Sekunde abgebaut wird
+const TRAUMA_DECAY: f32 = 1.2;
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CameraControl>().add_systems(
-            Update,
-            (
-                camera_input_system,
-                camera_zoom_system,
-                camera_follow_system
-                    .after(camera_input_system)
-                    .after(camera_zoom_system),
+        app.init_resource::<CameraControl>()
+            .init_resource::<CameraShakeSettings>()
+            .init_resource::<CameraTrauma>()
+            .add_event::<CameraShake>()
+            .add_systems(
+                OnExit(AppState::GameOver),
+                (reset_camera_control, reset_camera_trauma),
             )
-                .run_if(in_state(AppState::Running)),
-        );
+            .add_systems(
+                Update,
+                (
+                    camera_input_system,
+                    camera_zoom_system,
+                    (
+                        remove_camera_shake,
+                        camera_follow_system,
+                        camera_trauma_system,
+                        apply_camera_shake,
+                    )
+                        .chain()
+                        .after(camera_input_system)
+                        .after(camera_zoom_system),
+                )
+                    .run_if(in_state(AppState::Running)),
+            );
     }
 }
 
@@ -45,6 +62,22 @@ impl Default for CameraControl {
     }
 }
 
+impl CameraControl {
+    // Blickrichtung der Kamera (von der Kamera zum LookAt-Punkt)
+    pub fn aim_direction(&self) -> Vec3 {
+        -Vec3::new(
+            self.yaw.sin() * self.pitch.cos(),
+            self.pitch.sin(),
+            self.yaw.cos() * self.pitch.cos(),
+        )
+        .normalize()
+    }
+}
+
+fn reset_camera_control(mut camera_control: ResMut<CameraControl>) {
+    *camera_control = CameraControl::default();
+}
+
 fn camera_input_system(
     mut camera_control: ResMut<CameraControl>,
     mut mouse_motion_events: EventReader<MouseMotion>,
@@ -68,12 +101,7 @@ fn camera_follow_system(
     let look_at = player_translation + look_at_offset;
 
     // Richtung von LookAt zur Kamera (aus Yaw und Pitch)
-    let dir = Vec3::new(
-        camera_control.yaw.sin() * camera_control.pitch.cos(),
-        camera_control.pitch.sin(),
-        camera_control.yaw.cos() * camera_control.pitch.cos(),
-    )
-    .normalize();
+    let dir = -camera_control.aim_direction();
 
     let target_pos = look_at + dir * camera_control.zoom;
 
@@ -100,3 +128,137 @@ fn camera_zoom_system(
     camera_control.zoom *= 1.0 + delta_y * CAMERA_ZOOM_SPEED;
     camera_control.zoom = camera_control.zoom.clamp(2.0, 30.0);
 }
+
+// Erschütterung der Kamera. Mit Ursprung nimmt sie mit dem Abstand zur Kamera ab,
+// ohne Ursprung (z.B. Treffer am Spieler) wirkt sie voll.
+#[derive(Event, Clone, Debug)]
+pub struct CameraShake {
+    pub intensity: f32, // Trauma, das hinzukommt (0.0 - 1.0)
+    pub origin: Option<Vec3>,
+    pub radius: f32, // ab hier ist nichts mehr zu spüren
+}
+
+impl CameraShake {
+    pub fn at(origin: Vec3, intensity: f32, radius: f32) -> Self {
+        CameraShake {
+            intensity,
+            origin: Some(origin),
+            radius,
+        }
+    }
+
+    pub fn global(intensity: f32) -> Self {
+        CameraShake {
+            intensity,
+            origin: None,
+            radius: f32::INFINITY,
+        }
+    }
+
+    // Trauma, das bei der Kamera ankommt
+    pub fn trauma_at(&self, camera: Vec3) -> f32 {
+        let Some(origin) = self.origin else {
+            return self.intensity;
+        };
+        let falloff = (1.0 - origin.distance(camera) / self.radius).clamp(0.0, 1.0);
+        self.intensity * falloff * falloff
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct CameraShakeSettings {
+    pub intensity: f32,  // globaler Faktor, 0.0 = aus
+    pub max_offset: f32, // Meter
+    pub max_angle: f32,  // Radiant
+    pub frequency: f32,  // Geschwindigkeit, mit der das Rauschen abgetastet wird
+}
+
+impl Default for CameraShakeSettings {
+    fn default() -> Self {
+        CameraShakeSettings {
+            intensity: 1.0,
+            max_offset: 0.4,
+            max_angle: 0.05,
+            frequency: 18.0,
+        }
+    }
+}
+
+// Aktuelles Trauma und der im letzten Frame aufaddierte Versatz
+#[derive(Resource)]
+pub struct CameraTrauma {
+    pub trauma: f32,
+    applied_offset: Vec3,
+    noise: Perlin,
+}
+
+impl Default for CameraTrauma {
+    fn default() -> Self {
+        CameraTrauma {
+            trauma: 0.0,
+            applied_offset: Vec3::ZERO,
+            noise: Perlin::new(rand::random()),
+        }
+    }
+}
+
+impl CameraTrauma {
+    // Stärke wächst quadratisch, damit kleine Erschütterungen dezent bleiben
+    pub fn shake(&self) -> f32 {
+        self.trauma * self.trauma
+    }
+
+    // Rauschwert in [-1, 1] für einen der Kanäle
+    fn sample(&self, channel: f64, t: f64) -> f32 {
+        self.noise.get([t, channel * 10.0]) as f32
+    }
+}
+
+fn reset_camera_trauma(mut trauma: ResMut<CameraTrauma>) {
+    trauma.trauma = 0.0;
+    trauma.applied_offset = Vec3::ZERO;
+}
+
+// Versatz des letzten Frames zurücknehmen, damit das Nachziehen nicht mitwackelt
+fn remove_camera_shake(
+    mut trauma: ResMut<CameraTrauma>,
+    mut camera_transform: Single<&mut Transform, With<Camera3d>>,
+) {
+    camera_transform.translation -= trauma.applied_offset;
+    trauma.applied_offset = Vec3::ZERO;
+}
+
+fn camera_trauma_system(
+    time: Res<Time>,
+    mut shake_events: EventReader<CameraShake>,
+    mut trauma: ResMut<CameraTrauma>,
+    camera_transform: Single<&Transform, With<Camera3d>>,
+) {
+    let mut value = (trauma.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+    for shake in shake_events.read() {
+        value += shake.trauma_at(camera_transform.translation);
+    }
+    trauma.trauma = value.min(1.0);
+}
+
+// Nach dem Nachziehen: Versatz und Drehung aus Rauschen
+fn apply_camera_shake(
+    time: Res<Time>,
+    settings: Res<CameraShakeSettings>,
+    mut trauma: ResMut<CameraTrauma>,
+    mut camera_transform: Single<&mut Transform, With<Camera3d>>,
+) {
+    let shake = trauma.shake() * settings.intensity;
+    if shake <= 0.0 {
+        return;
+    }
+    let t = (time.elapsed_secs() * settings.frequency) as f64;
+    let local_offset = Vec3::new(trauma.sample(0.0, t), trauma.sample(1.0, t), 0.0);
+    let offset = camera_transform.rotation * local_offset * settings.max_offset * shake;
+    let roll = trauma.sample(2.0, t) * settings.max_angle * shake;
+    let pitch = trauma.sample(3.0, t) * settings.max_angle * shake * 0.5;
+
+    camera_transform.translation += offset;
+    camera_transform.rotation *= Quat::from_euler(EulerRot::XYZ, pitch, 0.0, roll);
+    trauma.applied_offset = offset;
+}
@@ -1,9 +1,32 @@
-use crate::{AppState, Ground, Health, Player};
-use bevy::{audio, prelude::*, state::commands};
+use crate::{
+    AppState, Ground, Health, Player,
+    assets::enemy_archetypes::{EnemyArchetype, EnemyBehavior, EnemyWeapon},
+};
+use bevy::{audio, platform::collections::HashMap, prelude::*, state::commands};
+use bevy_common_assets::json::JsonAssetPlugin;
 use bevy_rapier3d::{na::RealField, prelude::*};
-use rand::Rng;
-
-use super::bullet::{Bullet, BulletLifetime};
+use rand::{Rng, seq::IndexedRandom};
+
+use super::{
+    aiming::{EnemyAim, enemy_aim_system},
+    asset_cache::GameAssetCache,
+    bullet::{Bullet, ProjectilePool, spawn_bullet},
+    enemy_ai::{AiConfig, EnemyBrain, RangedWeapon, enemy_ai_system},
+    explosion::{PendingExplosion, PendingExplosionSuppressed},
+    grounded::{Grounded, SelfRighting, UPRIGHT_THRESHOLD, ground_detection_system, uprightness},
+    health::{DamageEvent, DamageKind, Faction},
+    level::Wall,
+    perception::{Perception, enemy_perception_system},
+    score::ChainTracker,
+    tree::TreeCollider,
+};
+
+const ENEMY_ARCHETYPES: &[&str] = &[
+    "enemies/grunt.enemy.json",
+    "enemies/charger.enemy.json",
+    "enemies/sniper.enemy.json",
+    "enemies/kamikaze.enemy.json",
+];
 
 #[derive(Resource, Clone)]
 struct EnemyShootSound(Handle<AudioSource>);
@@ -12,63 +35,247 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup)
+        app.add_plugins(JsonAssetPlugin::<EnemyArchetype>::new(&["enemy.json"]))
+            .add_systems(Startup, setup)
+            .add_systems(OnExit(AppState::GameOver), clear_enemies)
             .add_systems(
                 Update,
                 (
                     enemy_movement_system,
                     enemy_shooting,
+                    enemy_melee_cooldown_system.before(enemy_contact_system),
+                    enemy_contact_system,
+                    enemy_hit_trigger_system,
+                    enemy_hit_reaction_system,
                     enemy_despawn_far_system,
-                    maybe_spawn_enemy.run_if(enemy_count_under_threshold),
                 )
+                    .after(enemy_perception_system)
+                    .after(enemy_aim_system)
+                    .after(enemy_ai_system)
+                    .after(ground_detection_system)
                     .run_if(in_state(AppState::Running)),
-            )
-            .insert_resource(EnemySpawnDelay {
-                timer: Timer::from_seconds(1.0, TimerMode::Once),
-            });
+            );
     }
 }
 
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let enemy_shoot_sound = asset_server.load("sounds/arrow_shoot.wav");
     commands.insert_resource(EnemyShootSound(enemy_shoot_sound));
+    commands.insert_resource(EnemyArchetypes {
+        handles: ENEMY_ARCHETYPES
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect(),
+    });
 }
 
 #[derive(Component)]
 pub struct Enemy {
     pub damage: f32,
-    pub material: Handle<StandardMaterial>,
+    pub color: Color,
+    pub material: Handle<StandardMaterial>, // geteilt mit allen Gegnern derselben Farbe
+    pub size: Vec3,
+    pub max_health: f32,
+    pub behavior: EnemyBehavior,
     pub max_turn_speed: f32,
     pub move_force: f32,
     pub max_speed: f32,
     pub turn_torque: f32,
 }
 
+// Kurzes Aufblitzen, wenn ein Gegner einen Treffer überlebt
+#[derive(Component)]
+pub struct HitReaction {
+    pub timer: Timer,
+    // Eigene Materialkopie nur für die Dauer des Aufblitzens
+    material: Option<Handle<StandardMaterial>>,
+}
+
+impl Default for HitReaction {
+    fn default() -> Self {
+        HitReaction {
+            timer: Timer::from_seconds(0.25, TimerMode::Once),
+            material: None,
+        }
+    }
+}
+
+// Überlebte Treffer lösen eine HitReaction aus.
+// Health wird erst in PostUpdate abgezogen, daher den Schaden des Frames selbst aufsummieren.
+fn enemy_hit_trigger_system(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    enemy_query: Query<&Health, (With<Enemy>, Without<PendingExplosion>)>,
+) {
+    let mut damage_taken: HashMap<Entity, f32> = HashMap::default();
+    for event in damage_events.read() {
+        if enemy_query.contains(event.target) {
+            *damage_taken.entry(event.target).or_default() += event.amount;
+        }
+    }
+    for (target, amount) in damage_taken {
+        if let Ok(health) = enemy_query.get(target)
+            && health.value - amount > 0.0
+        {
+            commands.entity(target).try_insert(HitReaction::default());
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn enemy_hit_reaction_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            Entity,
+            &Enemy,
+            &mut HitReaction,
+            &mut MeshMaterial3d<StandardMaterial>,
+        ),
+        Without<PendingExplosion>,
+    >,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, enemy, mut reaction, mut material) in query.iter_mut() {
+        let flash = match &reaction.material {
+            Some(flash) => flash.clone(),
+            None => {
+                // Bei erneutem Treffer die noch aktive Kopie weiterverwenden
+                let flash = if material.0 != enemy.material {
+                    material.0.clone()
+                } else {
+                    let base = materials.get(&enemy.material).cloned().unwrap_or_default();
+                    materials.add(base)
+                };
+                material.0 = flash.clone();
+                reaction.material = Some(flash.clone());
+                flash
+            }
+        };
+
+        reaction.timer.tick(time.delta());
+        let t = reaction.timer.fraction();
+
+        // Von Weiß zurück zur Grundfarbe
+        if let Some(mat) = materials.get_mut(&flash) {
+            mat.base_color = Color::WHITE.mix(&enemy.color, t);
+            mat.emissive = LinearRgba::WHITE * (1.0 - t) * 4.0;
+        }
+
+        if reaction.timer.finished() {
+            // Zurück zum geteilten Material, die Kopie wird freigegeben
+            material.0 = enemy.material.clone();
+            commands.entity(entity).remove::<HitReaction>();
+        }
+    }
+}
+
+// Fühler für die Hindernisvermeidung: Winkel (Bogenmaß) und Gewicht
+const AVOIDANCE_FEELERS: [(f32, f32); 5] =
+    [(0.0, 1.0), (0.4, 0.7), (-0.4, 0.7), (0.9, 0.4), (-0.9, 0.4)];
+const AVOIDANCE_LOOKAHEAD: f32 = 2.5;
+const AVOIDANCE_LOOKAHEAD_PER_SPEED: f32 = 0.8;
+const AVOIDANCE_STRENGTH: f32 = 2.0;
+
+// Maximale Abweichung (Bogenmaß) zwischen Blickrichtung und Vorhalt beim Schuss
+const AIM_TOLERANCE: f32 = 0.15;
+
+// Ein Treffer eines Fühlers: Normale (XZ), relativer Abstand 0..1 und Gewicht
+pub struct FeelerHit {
+    pub normal: Vec3,
+    pub fraction: f32,
+    pub weight: f32,
+}
+
+// Lenkt die gewünschte Richtung um Hindernisse herum.
+// Nahe Treffer drücken stärker weg; geradeaus wird tangential am Hindernis vorbeigelenkt.
+pub fn avoidance_direction(desired: Vec3, hits: &[FeelerHit]) -> Vec3 {
+    let mut steer = Vec3::ZERO;
+    for hit in hits {
+        let urgency = (1.0 - hit.fraction).clamp(0.0, 1.0) * hit.weight;
+        let normal = hit.normal.with_y(0.0).normalize_or_zero();
+        // Tangente wählen, die am wenigsten von der Wunschrichtung abweicht
+        let mut tangent = Vec3::new(-normal.z, 0.0, normal.x);
+        if tangent.dot(desired) < 0.0 {
+            tangent = -tangent;
+        }
+        steer += (normal * 0.5 + tangent) * urgency;
+    }
+    if steer == Vec3::ZERO {
+        return desired;
+    }
+    (desired + steer * AVOIDANCE_STRENGTH).normalize_or(desired)
+}
+
+fn cast_avoidance_feelers(
+    rapier_context: &RapierContext,
+    filter: QueryFilter,
+    origin: Vec3,
+    direction: Vec3,
+    lookahead: f32,
+) -> Vec<FeelerHit> {
+    AVOIDANCE_FEELERS
+        .iter()
+        .filter_map(|(angle, weight)| {
+            let ray_dir = Quat::from_rotation_y(*angle) * direction;
+            let length = lookahead * angle.cos().max(0.5);
+            rapier_context
+                .cast_ray_and_get_normal(origin, ray_dir, length, true, filter)
+                .map(|(_, hit)| FeelerHit {
+                    normal: hit.normal,
+                    fraction: hit.time_of_impact / length,
+                    weight: *weight,
+                })
+        })
+        .collect()
+}
+
+#[allow(clippy::type_complexity)]
 fn enemy_movement_system(
     time: Res<Time>,
+    rapier_context: ReadRapierContext,
     player_query: Single<&Transform, With<Player>>,
     mut enemy_query: Query<(
+        Entity,
         &Enemy,
         &Transform,
         &Velocity,
-        &mut EnemyMovement,
+        &Perception,
+        &EnemyBrain,
+        &Grounded,
+        Option<&EnemyAim>,
         &mut ExternalForce,
     )>,
+    obstacle_query: Query<(), Or<(With<TreeCollider>, With<Wall>)>>,
 ) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
     let player_pos = player_query.translation;
-
-    for (enemy, enemy_transform, velocity, mut movement, mut force) in enemy_query.iter_mut() {
-        // Prüfe, ob Gegner auf dem Boden ist (Y-Position nahe 0.5)
-        if (enemy_transform.translation.y - 0.5).abs() > 0.06 {
-            // Nicht auf dem Boden: Keine Bewegungskraft anwenden!
+    let is_obstacle = |e: Entity| obstacle_query.contains(e);
+
+    for (entity, enemy, enemy_transform, velocity, perception, brain, grounded, aim, mut force) in
+        enemy_query.iter_mut()
+    {
+        // In der Luft oder umgekippt: keine Bewegungskraft (Aufrichten übernimmt SelfRighting)
+        if !grounded.on_ground || uprightness(enemy_transform.rotation) < UPRIGHT_THRESHOLD {
+            force.force = Vec3::ZERO;
+            force.torque = Vec3::ZERO;
             continue;
         }
 
         // --- Rotation ---
+        // Ziel ist der Spieler, wenn sichtbar, sonst seine letzte bekannte Position
         let enemy_pos = enemy_transform.translation;
-        let to_player = Vec3::new(player_pos.x, enemy_pos.y, player_pos.z) - enemy_pos;
+        let target_pos = perception.target_position(player_pos);
         let forward = enemy_transform.forward();
-        let target_dir = to_player.normalize_or_zero();
+        // Schützen drehen sich zum Vorhaltepunkt statt direkt zum Spieler
+        let target_dir = match (aim.and_then(EnemyAim::facing), target_pos) {
+            (Some(facing), _) => facing,
+            (None, Some(target)) => (target.with_y(enemy_pos.y) - enemy_pos).normalize_or_zero(),
+            (None, None) => brain.direction,
+        };
 
         if target_dir.length_squared() > 0.0 {
             let angle = forward.angle_between(target_dir);
@@ -86,39 +293,35 @@ fn enemy_movement_system(
             force.torque = Vec3::ZERO;
         }
 
-        // --- Zufällige Bewegungsrichtung ---
-        movement.change_timer.tick(time.delta());
-        if movement.change_timer.just_finished() {
-            let mut rng = rand::rng();
-
-            // Richtung zum Spieler (nur XZ-Ebene)
-            let to_player = (player_pos - enemy_transform.translation).normalize_or_zero();
-            let player_dir = Vec3::new(to_player.x, 0.0, to_player.z).normalize_or_zero();
-
-            // Gewichtung: 70% Richtung Spieler, 30% bisherige Richtung
-            let weight_player = 0.8;
-            let weight_old = 1.0 - weight_player;
-            let mut new_dir =
-                (player_dir * weight_player + movement.direction * weight_old).normalize_or_zero();
-
-            // Kleine Zufallsrotation (z.B. -0.3 bis 0.3 Bogenmaß)
-            let angle = rng.random_range(-0.3..0.3);
-            let rot = Quat::from_rotation_y(angle);
-            movement.direction = (rot * new_dir).normalize_or_zero();
-
-            // Timer auf neuen Zufallswert setzen (z.B. 1.0 bis 3.0 Sekunden)
-            movement.change_timer =
-                Timer::from_seconds(rng.random_range(1.0..3.0), TimerMode::Once);
-        }
+        // --- Hindernisvermeidung ---
+        // brain.direction ist bereits in Weltkoordinaten (XZ)
+        let speed = velocity.linvel.with_y(0.0).length();
+        let lookahead = AVOIDANCE_LOOKAHEAD + speed * AVOIDANCE_LOOKAHEAD_PER_SPEED;
+        let hits = if brain.direction == Vec3::ZERO {
+            Vec::new()
+        } else {
+            let filter = QueryFilter::default()
+                .exclude_rigid_body(entity)
+                .exclude_sensors()
+                .predicate(&is_obstacle);
+            cast_avoidance_feelers(
+                &rapier_context,
+                filter,
+                enemy_pos,
+                brain.direction,
+                lookahead + enemy.size.x / 2.0,
+            )
+        };
 
         // --- Bewegung mit Force ---
-        let world_dir = enemy_transform.rotation * movement.direction;
+        let world_dir = avoidance_direction(brain.direction, &hits);
+        let max_speed = enemy.max_speed * brain.speed_factor();
         let vel_in_dir = velocity.linvel.dot(world_dir);
 
         let force_in_dir = force.force.dot(world_dir);
         let force_rest = force.force - world_dir * force_in_dir;
 
-        if vel_in_dir < enemy.max_speed {
+        if vel_in_dir < max_speed {
             force.force = force_rest + world_dir * enemy.move_force;
         } else {
             force.force = force_rest;
@@ -126,6 +329,13 @@ fn enemy_movement_system(
     }
 }
 
+// Beim Neustart alle Gegner entfernen
+fn clear_enemies(mut commands: Commands, enemy_query: Query<Entity, With<Enemy>>) {
+    for entity in enemy_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
 fn enemy_despawn_far_system(
     mut commands: Commands,
     player_query: Single<&Transform, With<Player>>,
@@ -139,150 +349,304 @@ fn enemy_despawn_far_system(
     }
 }
 
-fn maybe_spawn_enemy(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut spawn_delay: ResMut<EnemySpawnDelay>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    player_query: Single<&Transform, With<Player>>,
-) {
+// Multiplikatoren, mit denen z.B. Wellen die Gegner stärker machen
+#[derive(Clone, Copy, Debug)]
+pub struct EnemyScaling {
+    pub damage: f32,
+    pub max_speed: f32,
+    pub shoot_rate: f32, // > 1.0 = schnelleres Schießen
+    pub accuracy: f32,
+}
+
+impl Default for EnemyScaling {
+    fn default() -> Self {
+        EnemyScaling {
+            damage: 1.0,
+            max_speed: 1.0,
+            shoot_rate: 1.0,
+            accuracy: 1.0,
+        }
+    }
+}
+
+// Handles aller bekannten Gegnertypen
+#[derive(Resource)]
+pub struct EnemyArchetypes {
+    pub handles: Vec<Handle<EnemyArchetype>>,
+}
+
+impl EnemyArchetypes {
+    // Gewichtete Zufallsauswahl unter den geladenen Typen.
+    // Ist `weights` nicht leer, überschreibt es die Gewichte der Typen (per Name).
+    pub fn choose<'a>(
+        &self,
+        assets: &'a Assets<EnemyArchetype>,
+        weights: &HashMap<String, f32>,
+    ) -> Option<&'a EnemyArchetype> {
+        let candidates: Vec<(&EnemyArchetype, f32)> = self
+            .handles
+            .iter()
+            .filter_map(|handle| assets.get(handle))
+            .map(|archetype| {
+                let weight = if weights.is_empty() {
+                    archetype.weight
+                } else {
+                    weights.get(&archetype.name).copied().unwrap_or(0.0)
+                };
+                (archetype, weight)
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        candidates
+            .choose_weighted(&mut rand::rng(), |(_, weight)| *weight)
+            .ok()
+            .map(|(archetype, _)| *archetype)
+    }
+}
+
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    cache: &mut GameAssetCache,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    archetype: &EnemyArchetype,
+    position: Vec3,
+    scaling: EnemyScaling,
+) -> Entity {
     let mut rng = rand::rng();
-    spawn_delay.timer.tick(time.delta());
-    if spawn_delay.timer.just_finished() {
-        let player_pos = player_query.translation;
-        let angle = rng.random_range(0.0..std::f32::consts::TAU);
-        let dist = rng.random_range(20.0..40.0);
-        let x = player_pos.x + dist * angle.cos();
-        let z = player_pos.z + dist * angle.sin();
-
-        let enemy_health = rng.random_range(50.0..=150.0);
-        let enemy_damage = rng.random_range(5.0..=20.0);
-
-        let enemy_material = materials.add(Color::from(Srgba::new(1.0, 0.2, 0.2, 1.0)));
-        commands.spawn((
-            Enemy {
-                damage: enemy_damage,
-                material: enemy_material.clone(),
-                max_turn_speed: std::f32::consts::PI * 0.5,
-                move_force: 7.0,
-                max_speed: 3.0,
-                turn_torque: 20.0,
-            },
-            Damping {
-                linear_damping: 0.8, // z.B. 0.5–2.0 testen
-                angular_damping: 0.4,
+    let enemy_health = rng.random_range(archetype.health_range());
+    let enemy_damage = rng.random_range(archetype.damage_range()) * scaling.damage;
+
+    let size = Vec3::from(archetype.size);
+    let [r, g, b] = archetype.color;
+    let enemy_color = Color::srgb(r, g, b);
+    let enemy_material = cache.color_material(materials, enemy_color);
+    let physics = &archetype.physics;
+    let movement = &archetype.movement;
+    let perception = &archetype.perception;
+    let ai = &archetype.ai;
+    let ai_config = AiConfig {
+        idle_distance: ai.idle_distance,
+        engage_distance: ai.engage_distance,
+        flee_health: ai.flee_health,
+        safe_distance: ai.safe_distance,
+        can_strafe: !archetype.behavior.charge,
+    };
+
+    let mut enemy = commands.spawn((
+        Enemy {
+            damage: enemy_damage,
+            color: enemy_color,
+            material: enemy_material.clone(),
+            size,
+            max_health: enemy_health,
+            behavior: archetype.behavior.clone(),
+            max_turn_speed: movement.max_turn_speed,
+            move_force: movement.move_force,
+            max_speed: movement.max_speed * scaling.max_speed,
+            turn_torque: movement.turn_torque,
+        },
+        Damping {
+            linear_damping: physics.linear_damping,
+            angular_damping: physics.angular_damping,
+        },
+        Mesh3d(cache.cuboid(meshes, size)),
+        MeshMaterial3d(enemy_material),
+        Transform::from_translation(position.with_y(size.y / 2.0)),
+        Visibility::Visible,
+        RigidBody::Dynamic,
+        Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+        ActiveEvents::COLLISION_EVENTS,
+        ExternalImpulse::default(),
+        ExternalForce::default(),
+        Velocity::default(),
+        (
+            Health {
+                value: enemy_health,
             },
-            Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
-            MeshMaterial3d(enemy_material),
-            Transform::from_xyz(x, 0.5, z),
-            Visibility::Visible,
-            RigidBody::Dynamic,
-            Collider::cuboid(0.5, 0.5, 0.5),
-            ActiveEvents::COLLISION_EVENTS,
-            EnemyShootTimer {
-                timer: Timer::from_seconds(5.0, TimerMode::Repeating),
+            Friction {
+                coefficient: physics.friction,
+                combine_rule: CoefficientCombineRule::Average,
             },
-            EnemyMovement {
-                direction: Vec3::Z,
-                change_timer: Timer::from_seconds(rng.random_range(1.0..3.0), TimerMode::Once),
-            },
-            ExternalImpulse::default(),
-            ExternalForce::default(),
-            Velocity::default(),
-            (
-                Friction {
-                    coefficient: 0.1, // oder ein Wert nach Geschmack, z.B. 0.5–1.0
-                    combine_rule: CoefficientCombineRule::Average,
-                },
-                ColliderMassProperties::Density(2.0),
+            ColliderMassProperties::Density(physics.density),
+            Perception::new(
+                perception.view_distance,
+                perception.view_angle,
+                perception.memory_secs,
             ),
-        ));
-
-        // Nach jedem Spawn neuen zufälligen Delay setzen (z.B. 0.5 bis 2.5 Sekunden)
-        spawn_delay.timer = Timer::from_seconds(rng.random_range(0.5..2.5), TimerMode::Once);
+            Grounded::new(size / 2.0),
+            SelfRighting::default(),
+            ReadMassProperties::default(),
+        ),
+    ));
+
+    match archetype.weapon {
+        EnemyWeapon::None => {
+            enemy.insert(EnemyBrain::new(ai_config, None));
+        }
+        EnemyWeapon::Melee { cooldown } => {
+            enemy.insert((
+                EnemyBrain::new(ai_config, None),
+                EnemyMelee {
+                    cooldown: Timer::from_seconds(cooldown, TimerMode::Once),
+                },
+            ));
+        }
+        EnemyWeapon::Bullet {
+            interval,
+            bullet_speed,
+            recoil,
+            accuracy,
+            magazine,
+            reload_secs,
+        } => {
+            let weapon =
+                RangedWeapon::new(interval / scaling.shoot_rate, recoil, magazine, reload_secs);
+            enemy.insert((
+                EnemyBrain::new(ai_config, Some(weapon)),
+                EnemyAim::new(accuracy * scaling.accuracy, bullet_speed),
+            ));
+        }
     }
+    enemy.id()
 }
 
-fn enemy_count_under_threshold(enemy_query: Query<(), With<Enemy>>) -> bool {
-    enemy_query.iter().count() < 20
+fn enemy_melee_cooldown_system(time: Res<Time>, mut melee_query: Query<&mut EnemyMelee>) {
+    for mut melee in melee_query.iter_mut() {
+        melee.cooldown.tick(time.delta());
+    }
 }
 
-#[allow(clippy::too_many_arguments)]
-fn enemy_shooting(
+// Nahkampf und Kamikaze: reagieren auf Berührung mit dem Spieler
+#[allow(clippy::type_complexity)]
+fn enemy_contact_system(
     mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    mut enemy_query: Query<
+        (
+            &Enemy,
+            &Transform,
+            &mut ExternalImpulse,
+            Option<&mut EnemyMelee>,
+        ),
+        Without<PendingExplosion>,
+    >,
+    mut chain_tracker: ResMut<ChainTracker>,
     time: Res<Time>,
-    mut enemy_query: Query<(&Transform, &mut ExternalImpulse, &mut EnemyShootTimer), With<Enemy>>,
-    player_query: Single<&Transform, With<Player>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    enemy_shoot_sound: Res<EnemyShootSound>, // <--- NEU
 ) {
-    let bullet_speed = 30.0;
-    let player_pos = player_query.translation;
-
-    for (enemy_transform, mut impulse, mut shoot_timer) in enemy_query.iter_mut() {
-        shoot_timer.timer.tick(time.delta());
-        if !shoot_timer.timer.just_finished() {
+    let (player_entity, player_transform) = player.into_inner();
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = event else {
+            continue;
+        };
+        let enemy_entity = if *e1 == player_entity {
+            *e2
+        } else if *e2 == player_entity {
+            *e1
+        } else {
+            continue;
+        };
+        let Ok((enemy, transform, mut impulse, melee)) = enemy_query.get_mut(enemy_entity) else {
+            continue;
+        };
+
+        if enemy.behavior.explode_on_contact {
+            // Kurz hochhüpfen, beim Aufkommen explodiert er.
+            // Die Explosion geht auf das Konto der Gegner.
+            impulse.impulse += Vec3::Y * 4.0;
+            let chain = chain_tracker.start_chain(Faction::Enemy);
+            chain_tracker.register(enemy_entity, chain, time.elapsed_secs());
+            commands.entity(enemy_entity).try_insert((
+                PendingExplosion::new(0.5, 1.0, 8.0, Vec3::ONE, Vec3::splat(1.5), 3.0, 10.0),
+                PendingExplosionSuppressed::default(),
+                chain,
+            ));
             continue;
         }
 
-        // Richtung zum Spieler
-        let to_player = (player_pos - enemy_transform.translation).normalize();
-        let shoot_direction = enemy_transform.forward();
+        if let Some(mut melee) = melee
+            && melee.cooldown.finished()
+        {
+            melee.cooldown.reset();
+            damage_events.write(DamageEvent {
+                source: Some(enemy_entity),
+                target: player_entity,
+                amount: enemy.damage,
+                kind: DamageKind::Melee,
+                position: (transform.translation + player_transform.translation) / 2.0,
+            });
+        }
+    }
+}
 
-        let recoil_strength = 5.0; // Stärke des Rückstoßes nach Geschmack
-        let recoil_dir = -shoot_direction.normalize_or_zero();
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn enemy_shooting(
+    mut commands: Commands,
+    mut enemy_query: Query<(
+        Entity,
+        &Enemy,
+        &Transform,
+        &EnemyAim,
+        &mut ExternalImpulse,
+        &mut EnemyBrain,
+    )>,
+    cache: Res<GameAssetCache>,
+    mut pool: ResMut<ProjectilePool>,
+    enemy_shoot_sound: Res<EnemyShootSound>, // <--- NEU
+) {
+    let mut rng = rand::rng();
 
-        impulse.impulse += recoil_dir * recoil_strength;
+    for (enemy_entity, enemy, enemy_transform, aim, mut impulse, mut brain) in
+        enemy_query.iter_mut()
+    {
+        // Zustand, Schussabstand und Magazin entscheidet die KI
+        if !brain.can_fire() {
+            continue;
+        }
 
-        // Prüfe, ob Spieler im 90°-Sichtfeld vor dem Gegner ist
-        if shoot_direction.dot(to_player) > 0.0 {
-            // Beispiel im enemy_shooting-System (oder wo du die Bullet spawnst):
-            let bullet_offset = 1.0; // Abstand vor dem Gegner (z.B. 1 Meter)
-            let spawn_pos = enemy_transform.translation + shoot_direction * bullet_offset;
+        // Kein Vorhaltepunkt (z.B. keine Sichtlinie): nicht in die Bäume schießen
+        let Some(facing) = aim.facing() else {
+            continue;
+        };
 
-            commands.spawn((
-                Bullet,
-                BulletLifetime {
-                    timer: Timer::from_seconds(4.0, TimerMode::Once),
-                },
-                Restitution {
-                    coefficient: 1.0,
-                    combine_rule: CoefficientCombineRule::Max,
-                },
-                Mesh3d(meshes.add(Sphere::new(0.2))),
-                MeshMaterial3d(materials.add(Color::from(Srgba::new(1.0, 1.0, 0.0, 1.0)))),
-                Transform::from_translation(spawn_pos),
-                Visibility::Visible,
-                RigidBody::Dynamic,
-                Collider::ball(0.2),
-                Velocity::linear(shoot_direction * bullet_speed),
-                ActiveEvents::COLLISION_EVENTS,
-                ColliderMassProperties::Density(2.0),
-                Friction {
-                    coefficient: 0.1, // oder ein Wert nach Geschmack, z.B. 0.5–1.0
-                    combine_rule: CoefficientCombineRule::Average,
-                },
-                AudioPlayer::new(enemy_shoot_sound.0.clone()),
-                PlaybackSettings::ONCE.with_spatial(true),
-            ));
+        // Erst feuern, wenn der Gegner zum Vorhaltepunkt gedreht ist
+        let forward = enemy_transform.forward().with_y(0.0).normalize_or_zero();
+        if forward.angle_between(facing) > AIM_TOLERANCE {
+            continue;
         }
+        let Some(shot_velocity) = aim.shot_velocity(&mut rng) else {
+            continue;
+        };
+        let Some(weapon) = brain.weapon.as_mut() else {
+            continue;
+        };
+        weapon.fire();
+
+        let shoot_direction = shot_velocity.normalize_or_zero();
+        impulse.impulse -= shoot_direction * weapon.recoil_strength;
+
+        let bullet_offset = 1.0; // Abstand vor dem Gegner (z.B. 1 Meter)
+        let spawn_pos = enemy_transform.translation + shoot_direction * bullet_offset;
+
+        spawn_bullet(
+            &mut commands,
+            &mut pool,
+            &cache,
+            Bullet::new(enemy_entity, Faction::Enemy, enemy.damage),
+            spawn_pos,
+            shot_velocity,
+        );
+        commands.spawn((
+            AudioPlayer::new(enemy_shoot_sound.0.clone()),
+            PlaybackSettings::DESPAWN.with_spatial(true),
+            Transform::from_translation(spawn_pos),
+        ));
     }
 }
 
-#[derive(Resource)]
-struct EnemySpawnDelay {
-    timer: Timer,
-}
-
 #[derive(Component)]
-struct EnemyShootTimer {
-    timer: Timer,
-}
-
-#[derive(Component)]
-struct EnemyMovement {
-    direction: Vec3,
-    change_timer: Timer,
+struct EnemyMelee {
+    cooldown: Timer,
 }
@@ -0,0 +1,431 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{AppState, Health, Player};
+
+use super::{
+    enemy::Enemy,
+    perception::{Perception, enemy_perception_system},
+};
+
+pub struct EnemyAiPlugin;
+
+impl Plugin for EnemyAiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            enemy_ai_system
+                .after(enemy_perception_system)
+                .run_if(in_state(AppState::Running)),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AiState {
+    Idle,   // Spieler weit weg oder unbekannt: umherstreifen
+    Chase,  // Auf den Spieler bzw. seine letzte bekannte Position zu
+    Strafe, // In Reichweite: seitlich um den Spieler kreisen
+    Flee,   // Angeschlagen: Abstand gewinnen
+    Reload, // Magazin leer: zurückweichen, nicht schießen
+}
+
+// Schwellwerte eines Gegners für die Zustandswechsel
+#[derive(Clone, Debug)]
+pub struct AiConfig {
+    pub idle_distance: f32,   // weiter weg = Idle
+    pub engage_distance: f32, // näher dran (und Sicht) = Strafe
+    pub flee_health: f32,     // Anteil der Max-Health, ab dem geflohen wird
+    pub safe_distance: f32,   // bis hierhin wird geflohen
+    pub can_strafe: bool,
+}
+
+// Momentaufnahme aller Größen, von denen die Zustandswechsel abhängen
+#[derive(Clone, Copy, Debug)]
+pub struct AiInputs {
+    pub player_distance: f32,
+    pub target_known: bool, // sichtbar oder letzte Position bekannt
+    pub sees_player: bool,
+    pub health_fraction: f32,
+    pub magazine_empty: bool,
+    pub reload_finished: bool,
+}
+
+// Reine Übergangsfunktion ohne ECS-Zugriff, damit sie für sich prüfbar ist.
+// Schwellen haben eine Hysterese, damit Gegner nicht an der Grenze flackern.
+pub fn next_state(state: AiState, inputs: &AiInputs, config: &AiConfig) -> AiState {
+    // Nachladen wird immer zu Ende gebracht
+    if state == AiState::Reload {
+        if !inputs.reload_finished {
+            return AiState::Reload;
+        }
+    } else if inputs.magazine_empty {
+        return AiState::Reload;
+    }
+
+    let hurt = inputs.health_fraction <= config.flee_health;
+    if hurt && inputs.target_known {
+        let flee_until = if state == AiState::Flee {
+            config.safe_distance * 1.2
+        } else {
+            config.safe_distance
+        };
+        if inputs.player_distance < flee_until {
+            return AiState::Flee;
+        }
+    }
+
+    if !inputs.target_known || inputs.player_distance > config.idle_distance {
+        return AiState::Idle;
+    }
+
+    if config.can_strafe && inputs.sees_player {
+        let strafe_until = if state == AiState::Strafe {
+            config.engage_distance * 1.25
+        } else {
+            config.engage_distance
+        };
+        if inputs.player_distance <= strafe_until {
+            return AiState::Strafe;
+        }
+    }
+
+    AiState::Chase
+}
+
+// Fernkampfwaffe mit Schussabstand und Magazin
+#[derive(Debug)]
+pub struct RangedWeapon {
+    pub cooldown: Timer,
+    pub recoil_strength: f32,
+    pub magazine: u32,
+    pub shots_left: u32,
+    pub reload: Timer,
+}
+
+impl RangedWeapon {
+    pub fn new(interval: f32, recoil_strength: f32, magazine: u32, reload_secs: f32) -> Self {
+        RangedWeapon {
+            cooldown: Timer::from_seconds(interval, TimerMode::Once),
+            recoil_strength,
+            magazine: magazine.max(1),
+            shots_left: magazine.max(1),
+            reload: Timer::from_seconds(reload_secs, TimerMode::Once),
+        }
+    }
+
+    pub fn fire(&mut self) {
+        self.shots_left = self.shots_left.saturating_sub(1);
+        self.cooldown.reset();
+    }
+}
+
+// Verhalten eines Gegners: aktueller Zustand, Laufrichtung und Waffe
+#[derive(Component, Debug)]
+pub struct EnemyBrain {
+    pub state: AiState,
+    pub config: AiConfig,
+    pub direction: Vec3, // gewünschte Laufrichtung in Weltkoordinaten (XZ)
+    pub change_timer: Timer,
+    pub strafe_sign: f32,
+    pub weapon: Option<RangedWeapon>,
+}
+
+impl EnemyBrain {
+    pub fn new(config: AiConfig, weapon: Option<RangedWeapon>) -> Self {
+        let mut rng = rand::rng();
+        EnemyBrain {
+            state: AiState::Idle,
+            config,
+            direction: Vec3::Z,
+            change_timer: Timer::from_seconds(rng.random_range(1.0..3.0), TimerMode::Once),
+            strafe_sign: if rng.random_bool(0.5) { 1.0 } else { -1.0 },
+            weapon,
+        }
+    }
+
+    // Geschossen wird nur in Zuständen mit Blick auf den Spieler
+    pub fn can_fire(&self) -> bool {
+        matches!(self.state, AiState::Chase | AiState::Strafe | AiState::Flee)
+            && self
+                .weapon
+                .as_ref()
+                .is_some_and(|weapon| weapon.cooldown.finished() && weapon.shots_left > 0)
+    }
+
+    // Anteil der Höchstgeschwindigkeit je Zustand
+    pub fn speed_factor(&self) -> f32 {
+        match self.state {
+            AiState::Idle => 0.5,
+            AiState::Strafe | AiState::Reload => 0.8,
+            AiState::Chase | AiState::Flee => 1.0,
+        }
+    }
+
+    fn choose_direction(
+        &mut self,
+        enemy: &Enemy,
+        to_target: Vec3,
+        distance: f32,
+        sees_player: bool,
+    ) {
+        let mut rng = rand::rng();
+        let tangent = Vec3::new(-to_target.z, 0.0, to_target.x) * self.strafe_sign;
+
+        let (direction, secs) = match self.state {
+            AiState::Idle => {
+                // Umherstreifen, grob in Richtung Spieler
+                let angle = rng.random_range(-1.2..1.2);
+                let wander =
+                    Quat::from_rotation_y(angle) * (to_target * 0.3 + self.direction * 0.7);
+                (wander.normalize_or(to_target), rng.random_range(2.0..4.0))
+            }
+            AiState::Chase if enemy.behavior.charge => {
+                // Stur auf den Spieler zu, Richtung oft nachführen
+                (to_target, 0.3)
+            }
+            AiState::Chase if !sees_player => {
+                // Keine Sichtlinie: zur letzten bekannten Position, seitlich versetzt,
+                // um an verdeckenden Stämmen vorbeizukommen
+                let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+                (
+                    (to_target + tangent * sign * 0.6).normalize_or_zero(),
+                    rng.random_range(0.5..1.0),
+                )
+            }
+            AiState::Chase => {
+                // Gewichtung: 80% Richtung Spieler, 20% bisherige Richtung,
+                // dazu eine kleine Zufallsrotation
+                let new_dir = (to_target * 0.8 + self.direction * 0.2).normalize_or_zero();
+                let angle = rng.random_range(-0.3..0.3);
+                (
+                    (Quat::from_rotation_y(angle) * new_dir).normalize_or_zero(),
+                    rng.random_range(1.0..3.0),
+                )
+            }
+            AiState::Strafe => {
+                // Gelegentlich die Kreisrichtung wechseln
+                if rng.random_bool(0.1) {
+                    self.strafe_sign = -self.strafe_sign;
+                }
+                // Radial auf die Wunschentfernung korrigieren
+                let radial =
+                    ((distance - self.config.engage_distance * 0.8) / 5.0).clamp(-1.0, 1.0);
+                ((tangent + to_target * radial).normalize_or(tangent), 0.3)
+            }
+            AiState::Flee => ((-to_target + tangent * 0.4).normalize_or_zero(), 0.5),
+            AiState::Reload => ((-to_target * 0.5 + tangent).normalize_or_zero(), 0.5),
+        };
+
+        self.direction = direction;
+        self.change_timer = Timer::from_seconds(secs, TimerMode::Once);
+    }
+}
+
+pub fn enemy_ai_system(
+    time: Res<Time>,
+    player_query: Single<&Transform, With<Player>>,
+    mut enemy_query: Query<(&Enemy, &Transform, &Health, &Perception, &mut EnemyBrain)>,
+) {
+    let player_pos = player_query.translation;
+
+    for (enemy, transform, health, perception, mut brain) in enemy_query.iter_mut() {
+        let state = brain.state;
+        if let Some(weapon) = brain.weapon.as_mut() {
+            weapon.cooldown.tick(time.delta());
+            if state == AiState::Reload {
+                weapon.reload.tick(time.delta());
+            }
+        }
+
+        let inputs = AiInputs {
+            player_distance: transform.translation.distance(player_pos),
+            target_known: perception.target_position(player_pos).is_some(),
+            sees_player: perception.sees_player,
+            health_fraction: health.value / enemy.max_health,
+            magazine_empty: brain.weapon.as_ref().is_some_and(|w| w.shots_left == 0),
+            reload_finished: brain.weapon.as_ref().is_none_or(|w| w.reload.finished()),
+        };
+        let new_state = next_state(state, &inputs, &brain.config);
+
+        if new_state != state {
+            if let Some(weapon) = brain.weapon.as_mut() {
+                if new_state == AiState::Reload {
+                    weapon.reload.reset();
+                } else if state == AiState::Reload {
+                    weapon.shots_left = weapon.magazine;
+                }
+            }
+            brain.state = new_state;
+        }
+
+        // Neue Laufrichtung bei Zustandswechsel oder abgelaufenem Timer
+        brain.change_timer.tick(time.delta());
+        if new_state != state || brain.change_timer.finished() {
+            let target = perception.target_position(player_pos).unwrap_or(player_pos);
+            let offset = (target - transform.translation).with_y(0.0);
+            let to_target = offset.normalize_or_zero();
+            brain.choose_direction(enemy, to_target, offset.length(), perception.sees_player);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AiConfig {
+        AiConfig {
+            idle_distance: 40.0,
+            engage_distance: 15.0,
+            flee_health: 0.3,
+            safe_distance: 20.0,
+            can_strafe: true,
+        }
+    }
+
+    fn inputs(player_distance: f32) -> AiInputs {
+        AiInputs {
+            player_distance,
+            target_known: true,
+            sees_player: true,
+            health_fraction: 1.0,
+            magazine_empty: false,
+            reload_finished: false,
+        }
+    }
+
+    #[test]
+    fn empty_magazine_starts_reload() {
+        let inputs = AiInputs {
+            magazine_empty: true,
+            ..inputs(10.0)
+        };
+        assert_eq!(
+            next_state(AiState::Strafe, &inputs, &config()),
+            AiState::Reload
+        );
+    }
+
+    #[test]
+    fn reload_stays_until_finished() {
+        // Auch wenn das Magazin schon als voll gemeldet wird
+        let waiting = inputs(10.0);
+        assert_eq!(
+            next_state(AiState::Reload, &waiting, &config()),
+            AiState::Reload
+        );
+
+        let hurt = AiInputs {
+            health_fraction: 0.1,
+            ..inputs(5.0)
+        };
+        assert_eq!(
+            next_state(AiState::Reload, &hurt, &config()),
+            AiState::Reload
+        );
+
+        let finished = AiInputs {
+            reload_finished: true,
+            ..inputs(10.0)
+        };
+        assert_eq!(
+            next_state(AiState::Reload, &finished, &config()),
+            AiState::Strafe
+        );
+    }
+
+    #[test]
+    fn flee_has_hysteresis() {
+        let hurt = |distance| AiInputs {
+            health_fraction: 0.2,
+            ..inputs(distance)
+        };
+        // Betreten nur innerhalb von safe_distance
+        assert_eq!(
+            next_state(AiState::Chase, &hurt(19.0), &config()),
+            AiState::Flee
+        );
+        assert_ne!(
+            next_state(AiState::Chase, &hurt(22.0), &config()),
+            AiState::Flee
+        );
+        // Verlassen erst ab safe_distance * 1.2
+        assert_eq!(
+            next_state(AiState::Flee, &hurt(22.0), &config()),
+            AiState::Flee
+        );
+        assert_ne!(
+            next_state(AiState::Flee, &hurt(25.0), &config()),
+            AiState::Flee
+        );
+    }
+
+    #[test]
+    fn healthy_enemies_do_not_flee() {
+        assert_ne!(
+            next_state(AiState::Flee, &inputs(5.0), &config()),
+            AiState::Flee
+        );
+    }
+
+    #[test]
+    fn idle_when_far_or_forgotten() {
+        assert_eq!(
+            next_state(AiState::Chase, &inputs(50.0), &config()),
+            AiState::Idle
+        );
+
+        let forgotten = AiInputs {
+            target_known: false,
+            sees_player: false,
+            ..inputs(10.0)
+        };
+        assert_eq!(
+            next_state(AiState::Strafe, &forgotten, &config()),
+            AiState::Idle
+        );
+    }
+
+    #[test]
+    fn strafe_in_range_with_hysteresis() {
+        assert_eq!(
+            next_state(AiState::Chase, &inputs(10.0), &config()),
+            AiState::Strafe
+        );
+        // Schon beim Umkreisen: bis engage_distance * 1.25 weiter umkreisen
+        assert_eq!(
+            next_state(AiState::Strafe, &inputs(18.0), &config()),
+            AiState::Strafe
+        );
+        assert_eq!(
+            next_state(AiState::Chase, &inputs(18.0), &config()),
+            AiState::Chase
+        );
+    }
+
+    #[test]
+    fn chase_without_sight_or_strafing() {
+        let hidden = AiInputs {
+            sees_player: false,
+            ..inputs(10.0)
+        };
+        assert_eq!(
+            next_state(AiState::Idle, &hidden, &config()),
+            AiState::Chase
+        );
+
+        let charger = AiConfig {
+            can_strafe: false,
+            ..config()
+        };
+        assert_eq!(
+            next_state(AiState::Idle, &inputs(10.0), &charger),
+            AiState::Chase
+        );
+        assert_eq!(
+            next_state(AiState::Idle, &inputs(30.0), &config()),
+            AiState::Chase
+        );
+    }
+}
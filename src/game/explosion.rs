@@ -1,60 +1,143 @@
-use bevy::{audio, prelude::*};
-use bevy_rapier3d::{na::RealField, prelude::*};
-use rand::Rng;
+use bevy::{audio, platform::collections::HashSet, prelude::*, scene::SceneInstance};
+use bevy_rapier3d::prelude::*;
 
 use crate::{AppState, Ground, game::tree::StakeSound};
 
 use super::{
-    bullet::Bullet,
+    bullet::{Bullet, FriendlyFireSettings, SpentProjectile},
+    camera::CameraShake,
     enemy::Enemy,
-    health::Health,
+    health::{DamageEvent, DamageKind, DeathEvent, Faction, HealEvent},
+    level::Wall,
+    particles::{ParticleEvent, ParticlePreset},
     player::Player,
-    tree::{RootParticleAssets, TreeCollider, TreeRoot, maybe_uproot_tree},
+    score::{ChainTracker, ExplosionChain, ScoreEvent, ScoreKind},
+    tree::{TreeCollider, TreeRoot, TreeUprootedEvent, maybe_uproot_tree},
 };
 
-fn pending_explosion_system(
+const EXPLOSION_DAMAGE: f32 = 150.0;
+const RAMMING_MIN_SPEED: f32 = 6.0;
+const RAMMING_DAMAGE_PER_SPEED: f32 = 8.0;
+// Geschwindigkeitsänderung durch die Druckwelle in der Todeszone
+const BLAST_SPEED: f32 = 15.0;
+// Anteil der Druckwelle, der als Drehimpuls wirkt
+const BLAST_TORQUE: f32 = 0.3;
+// Verzögerung, mit der eine Explosion andere in ihrer Todeszone auslöst
+const CHAIN_DELAY_SECS: f32 = 0.15;
+const CHAIN_DELAY_PER_METER: f32 = 0.04;
+// Höchstens so viele Bäume entwurzelt eine einzelne Explosion
+const MAX_UPROOTS_PER_EXPLOSION: usize = 8;
+
+// Kopien der Materialien eines aufladenden Objekts, damit geteilte
+// Materialien (z.B. aus Baum-Szenen) unverändert bleiben
+#[derive(Component)]
+struct ChargeVisual {
+    base_scale: Vec3,
+    materials: Vec<ChargedMaterial>,
+}
+
+struct ChargedMaterial {
+    entity: Entity,
+    original: Handle<StandardMaterial>,
+    charged: Handle<StandardMaterial>,
+    base_color: Color,
+    emissive: LinearRgba,
+}
+
+// Materialien aller Nachfahren (inkl. der Entity selbst) durch Kopien ersetzen
+#[allow(clippy::type_complexity)]
+fn charge_visual_setup_system(
     mut commands: Commands,
+    query: Query<
+        (Entity, &Transform, Has<SceneRoot>, Option<&SceneInstance>),
+        (With<PendingExplosion>, Without<ChargeVisual>),
+    >,
+    children_query: Query<&Children>,
+    scene_spawner: Res<SceneSpawner>,
+    mut material_query: Query<&mut MeshMaterial3d<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, transform, has_scene, instance) in query.iter() {
+        let mut charged_materials = Vec::new();
+        for descendant in std::iter::once(entity).chain(children_query.iter_descendants(entity)) {
+            let Ok(mut material) = material_query.get_mut(descendant) else {
+                continue;
+            };
+            let Some(original) = materials.get(&material.0).cloned() else {
+                continue;
+            };
+            let charged = materials.add(original.clone());
+            charged_materials.push(ChargedMaterial {
+                entity: descendant,
+                original: std::mem::replace(&mut material.0, charged.clone()),
+                charged,
+                base_color: original.base_color,
+                emissive: original.emissive,
+            });
+        }
+        // Szene noch nicht instanziiert: nächsten Frame erneut versuchen
+        let scene_pending =
+            has_scene && !instance.is_some_and(|id| scene_spawner.instance_is_ready(**id));
+        if charged_materials.is_empty() && scene_pending {
+            continue;
+        }
+        commands.entity(entity).try_insert(ChargeVisual {
+            base_scale: transform.scale,
+            materials: charged_materials,
+        });
+    }
+}
+
+// Aufhellen, Glühen und Anwachsen über die Laufzeit des Timers
+fn pending_explosion_system(
     time: Res<Time>,
-    mut query: Query<&mut PendingExplosion>,
+    mut query: Query<(&mut PendingExplosion, &ChargeVisual, &mut Transform)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    for mut anim in query.iter_mut() {
+    for (mut anim, visual, mut transform) in query.iter_mut() {
         anim.timer.tick(time.delta());
-        let t = (anim.timer.elapsed_secs() / anim.timer.duration().as_secs_f32()).clamp(0.0, 1.0);
-
-        // TODO: Make that generic
-        // Interpolierte Farbe (von start_color zu end_color)
-        // let Srgba {
-        //     red: sr,
-        //     green: sg,
-        //     blue: sb,
-        //     alpha: sa,
-        // } = anim.start_color.to_srgba();
-        // let Srgba {
-        //     red: er,
-        //     green: eg,
-        //     blue: eb,
-        //     alpha: ea,
-        // } = anim.end_color.to_srgba();
-        // let color = Color::srgba(
-        //     sr + t * (er - sr),
-        //     sg + t * (eg - sg),
-        //     sb + t * (eb - sb),
-        //     sa + t * (ea - sa),
-        // );
-
-        // if let Some(mat) = materials.get_mut(material_handle) {
-        //     mat.base_color = color;
-
-        //     // Emissive-Farbe interpolieren
-        //     let emissive_strength = t * anim.emissive_strength;
-        //     mat.emissive = LinearRgba::new(
-        //         1.0 * emissive_strength,
-        //         1.0 * emissive_strength,
-        //         0.2 * emissive_strength,
-        //         1.0,
-        //     );
-        // }
+        let t = anim.timer.fraction();
+
+        transform.scale = visual.base_scale * anim.start_scale.lerp(anim.end_scale, t);
+
+        let emissive_strength = t * anim.emissive_strength;
+        for charged in &visual.materials {
+            let Some(material) = materials.get_mut(&charged.charged) else {
+                continue;
+            };
+            material.base_color = charged.base_color.mix(
+                &Color::WHITE,
+                (t * anim.lighten_factor * 0.5).clamp(0.0, 1.0),
+            );
+            material.emissive = charged.emissive
+                + LinearRgba::new(
+                    1.0 * emissive_strength,
+                    1.0 * emissive_strength,
+                    0.2 * emissive_strength,
+                    0.0,
+                );
+        }
+    }
+}
+
+// Wird die PendingExplosion entfernt, ohne dass die Entity explodiert,
+// bekommt sie ihre Originale zurück. Beim Despawn werden die Kopien
+// mit dem letzten Handle freigegeben.
+fn charge_visual_restore_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &ChargeVisual, &mut Transform), Without<PendingExplosion>>,
+    mut material_query: Query<&mut MeshMaterial3d<StandardMaterial>>,
+) {
+    for (entity, visual, mut transform) in query.iter_mut() {
+        transform.scale = visual.base_scale;
+        for charged in &visual.materials {
+            if let Ok(mut material) = material_query.get_mut(charged.entity)
+                && material.0 == charged.charged
+            {
+                material.0 = charged.original.clone();
+            }
+        }
+        commands.entity(entity).remove::<ChargeVisual>();
     }
 }
 
@@ -109,78 +192,28 @@ impl Default for PendingExplosion {
 #[derive(Resource, Clone)]
 struct ExplosionSound(Handle<AudioSource>);
 
-#[derive(Component)]
-struct ExplosionParticle {
-    velocity: Vec3,
-    timer: Timer,
-    start_color: Color,
-    end_color: Color,
-    material: Handle<StandardMaterial>, // <--- NEU
-}
-
-fn explosion_particle_system(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut query: Query<(Entity, &mut ExplosionParticle, &mut Transform)>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    for (entity, mut particle, mut transform) in query.iter_mut() {
-        transform.translation += particle.velocity * time.delta_secs();
-        particle.timer.tick(time.delta());
-
-        let t = (particle.timer.elapsed_secs() / particle.timer.duration().as_secs_f32())
-            .clamp(0.0, 1.0);
-        let Srgba {
-            red: start_r,
-            green: start_g,
-            blue: start_b,
-            alpha: start_a,
-        } = particle.start_color.to_srgba();
-        let Srgba {
-            red: end_r,
-            green: end_g,
-            blue: end_b,
-            alpha: end_a,
-        } = particle.end_color.to_srgba();
-        let r = start_r + t * (end_r - start_r);
-        let g = start_g + t * (end_g - start_g);
-        let b = start_b + t * (end_b - start_b);
-        let a = start_a + t * (end_a - start_a);
-        let color = Color::srgba(r, g, b, a);
-
-        if let Some(mat) = materials.get_mut(&particle.material) {
-            mat.base_color = color;
-        }
-
-        if particle.timer.finished() {
-            commands.entity(entity).despawn();
-        }
-    }
-}
 #[allow(clippy::type_complexity)]
 #[allow(clippy::too_many_arguments)]
 fn ground_explosion_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     pending_explosions: Query<
-        (&Transform, Entity),
-        (With<PendingExplosion>, Without<PendingExplosionSuppressed>),
-    >,
-    children_query: Query<&ChildOf>,
-    mut all_enemies: Query<
-        (Entity, &Transform, &mut ExternalImpulse),
         (
-            With<Enemy>,
-            Without<DelayedDeath>,
-            Without<PendingExplosion>,
+            &Transform,
+            Entity,
+            &PendingExplosion,
+            Option<&ExplosionChain>,
         ),
+        (With<PendingExplosion>, Without<PendingExplosionSuppressed>),
     >,
-    mut player_health: Single<&mut Health, With<Player>>,
+    children_query: Query<&ChildOf>,
+    mut explosion_events: EventWriter<ExplosionEvent>,
     ground_entity: Single<Entity, With<Ground>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut particle_events: EventWriter<ParticleEvent>,
     explosion_sound: Res<ExplosionSound>,
 ) {
+    // Mehrere Collider eines Körpers melden sich im selben Frame einzeln
+    let mut detonated = HashSet::new();
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
             let other = if *e1 == *ground_entity {
@@ -190,7 +223,7 @@ fn ground_explosion_system(
             } else {
                 continue;
             };
-            let Some((explosion_transform, pending_entity)) =
+            let Some((explosion_transform, pending_entity, explosion, chain)) =
                 pending_explosions.get(*other).ok().or_else(|| {
                     children_query
                         .get(*other)
@@ -200,15 +233,18 @@ fn ground_explosion_system(
             else {
                 continue;
             };
+            if !detonated.insert(pending_entity) {
+                continue;
+            }
 
             explode_pending_entity(
                 &mut commands,
                 pending_entity,
                 explosion_transform.translation,
-                &mut all_enemies,
-                &mut player_health,
-                &mut meshes,
-                &mut materials,
+                explosion,
+                chain.copied(),
+                &mut explosion_events,
+                &mut particle_events,
                 &explosion_sound,
             );
         }
@@ -221,135 +257,597 @@ fn explode_pending_entity(
     commands: &mut Commands,
     entity: Entity,
     explosion_pos: Vec3,
-    all_enemies: &mut Query<
-        (Entity, &Transform, &mut ExternalImpulse),
+    explosion: &PendingExplosion,
+    chain: Option<ExplosionChain>,
+    explosion_events: &mut EventWriter<ExplosionEvent>,
+    particle_events: &mut EventWriter<ParticleEvent>,
+    explosion_sound: &Res<ExplosionSound>,
+) {
+    particle_events.write(ParticleEvent::new(ParticlePreset::Explosion, explosion_pos));
+    commands.spawn((
+        AudioPlayer::new(explosion_sound.0.clone()),
+        PlaybackSettings::DESPAWN.with_spatial(true),
+        Transform::from_translation(explosion_pos),
+    ));
+
+    // Wirkung auf die Umgebung übernehmen die Systeme, die das Event lesen
+    explosion_events.write(ExplosionEvent {
+        source: entity,
+        position: explosion_pos,
+        dead_zone_radius: explosion.dead_zone_radius,
+        affected_zone_radius: explosion.affected_zone_radius,
+        chain,
+    });
+    // Entity entfernen
+    commands.entity(entity).despawn();
+}
+
+// Eine PendingExplosion ist detoniert
+#[derive(Event, Clone, Debug)]
+pub struct ExplosionEvent {
+    pub source: Entity,
+    pub position: Vec3,
+    pub dead_zone_radius: f32,
+    pub affected_zone_radius: f32,
+    pub chain: Option<ExplosionChain>,
+}
+
+// Volle Wirkung in der Todeszone, danach linear abnehmend bis zum Rand
+pub fn explosion_falloff(distance: f32, dead_zone_radius: f32, affected_zone_radius: f32) -> f32 {
+    if distance <= dead_zone_radius {
+        1.0
+    } else if distance >= affected_zone_radius {
+        0.0
+    } else {
+        1.0 - (distance - dead_zone_radius) / (affected_zone_radius - dead_zone_radius)
+    }
+}
+
+// Form, mit der die Wirkung zwischen Todeszone und Rand abnimmt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FalloffCurve {
+    Linear,
+    Quadratic,  // fällt schnell ab, nur nahe Explosionen sind gefährlich
+    SmoothStep, // lange fast volle Wirkung, dann steiler Abfall
+}
+
+impl FalloffCurve {
+    // Formt den linearen Abfall (1.0 innen, 0.0 am Rand) um
+    pub fn apply(self, linear: f32) -> f32 {
+        let x = linear.clamp(0.0, 1.0);
+        match self {
+            FalloffCurve::Linear => x,
+            FalloffCurve::Quadratic => x * x,
+            FalloffCurve::SmoothStep => x * x * (3.0 - 2.0 * x),
+        }
+    }
+}
+
+// Wie Explosionen auf den Spieler wirken (je nach Schwierigkeitsgrad anpassbar)
+#[derive(Resource, Clone, Debug)]
+pub struct ExplosionSettings {
+    pub player_damage: f32, // Schaden in der Todeszone
+    pub player_knockback: f32,
+    pub falloff: FalloffCurve,
+    pub line_of_sight: bool, // Bäume und Wände schirmen die Druckwelle ab
+    pub self_damage: bool,   // auch vom Spieler ausgelöste Explosionen verletzen ihn
+}
+
+impl Default for ExplosionSettings {
+    fn default() -> Self {
+        ExplosionSettings {
+            player_damage: 40.0,
+            player_knockback: 30.0,
+            falloff: FalloffCurve::Quadratic,
+            line_of_sight: true,
+            self_damage: true,
+        }
+    }
+}
+
+// Schaden und Rückstoß für den Spieler
+#[allow(clippy::type_complexity)]
+fn player_explosion_damage_system(
+    mut explosion_events: EventReader<ExplosionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    settings: Res<ExplosionSettings>,
+    rapier_context: ReadRapierContext,
+    player: Single<(Entity, &Transform, &mut ExternalImpulse), With<Player>>,
+    obstacle_query: Query<(), Or<(With<TreeCollider>, With<Wall>)>>,
+) {
+    let (player_entity, player_transform, mut impulse) = player.into_inner();
+    let is_obstacle = |e: Entity| obstacle_query.contains(e);
+
+    for explosion in explosion_events.read() {
+        // Vom Spieler ausgelöste Ketten verletzen ihn nur mit self_damage
+        let own_chain = explosion
+            .chain
+            .is_some_and(|chain| chain.instigator == Faction::Player);
+        if own_chain && !settings.self_damage {
+            continue;
+        }
+        let offset = player_transform.translation - explosion.position;
+        let dist = offset.length();
+        if dist > explosion.affected_zone_radius {
+            continue;
+        }
+
+        if settings.line_of_sight
+            && dist > f32::EPSILON
+            && let Ok(rapier_context) = rapier_context.single()
+        {
+            let filter = QueryFilter::default()
+                .exclude_rigid_body(explosion.source)
+                .exclude_rigid_body(player_entity)
+                .exclude_sensors()
+                .predicate(&is_obstacle);
+            if rapier_context
+                .cast_ray(explosion.position, offset / dist, dist, true, filter)
+                .is_some()
+            {
+                continue;
+            }
+        }
+
+        let falloff = settings.falloff.apply(explosion_falloff(
+            dist,
+            explosion.dead_zone_radius,
+            explosion.affected_zone_radius,
+        ));
+        if falloff <= 0.0 {
+            continue;
+        }
+        damage_events.write(DamageEvent {
+            source: Some(explosion.source),
+            target: player_entity,
+            amount: settings.player_damage * falloff,
+            kind: DamageKind::Explosion,
+            position: explosion.position,
+        });
+
+        let dir = offset.with_y(0.0).normalize_or_zero();
+        impulse.impulse += (dir + Vec3::Y * 0.5) * settings.player_knockback * falloff;
+    }
+}
+
+fn explosion_camera_shake_system(
+    mut explosion_events: EventReader<ExplosionEvent>,
+    mut shake_events: EventWriter<CameraShake>,
+) {
+    for explosion in explosion_events.read() {
+        shake_events.write(CameraShake::at(
+            explosion.position,
+            0.6,
+            explosion.affected_zone_radius * 4.0,
+        ));
+    }
+}
+
+// Schaden und Impuls für Gegner im Umkreis
+#[allow(clippy::type_complexity)]
+fn explosion_damage_system(
+    mut explosion_events: EventReader<ExplosionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    all_enemies: Query<
+        (Entity, &Transform),
         (
             With<Enemy>,
             Without<DelayedDeath>,
             Without<PendingExplosion>,
         ),
     >,
-    player_health: &mut Single<&mut Health, With<Player>>,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-    explosion_sound: &Res<ExplosionSound>,
 ) {
-    // Partikel-Explosion erzeugen (wie gehabt)
-    let num_particles = 10;
-    let mut rng = rand::rng();
-    for i in 0..num_particles {
-        let dir = Vec3::new(
-            rng.random_range(-1.0..1.0),
-            rng.random_range(0.0..1.0),
-            rng.random_range(-1.0..1.0),
-        )
-        .normalize_or_zero();
-        let speed = rng.random_range(8.0..18.0);
-
-        let start_r = (1.0 + rng.random_range(-0.05..0.05)).clamp(0.9, 1.0);
-        let start_g = (0.2 + rng.random_range(-0.05..0.05)).clamp(0.1, 0.3);
-        let start_b = rng.random_range(0.0..0.05);
-        let end_r = (1.0 + rng.random_range(-0.05..0.05)).clamp(0.9, 1.0);
-        let end_g = (0.8 + rng.random_range(-0.1..0.1)).clamp(0.6, 1.0);
-        let end_b = rng.random_range(0.0..0.1);
-
-        let start_color = Color::srgba(start_r, start_g, start_b, 1.0);
-        let end_color = Color::srgba(end_r, end_g, end_b, 0.0);
-
-        let mat_handle = materials.add(start_color);
-        let mut e = commands.spawn((
-            ExplosionParticle {
-                velocity: dir * speed,
-                timer: Timer::from_seconds(rng.random_range(0.3..0.7), TimerMode::Once),
-                start_color,
-                end_color,
-                material: mat_handle.clone(),
+    for explosion in explosion_events.read() {
+        for (other_entity, other_transform) in all_enemies.iter() {
+            let dist = (other_transform.translation - explosion.position).length();
+            if dist > explosion.affected_zone_radius {
+                continue;
+            }
+            let falloff = explosion_falloff(
+                dist,
+                explosion.dead_zone_radius,
+                explosion.affected_zone_radius,
+            );
+            damage_events.write(DamageEvent {
+                source: Some(explosion.source),
+                target: other_entity,
+                amount: EXPLOSION_DAMAGE * falloff,
+                kind: DamageKind::Explosion,
+                position: explosion.position,
+            });
+        }
+    }
+}
+
+// Druckwelle für alle dynamischen Körper im Wirkungsbereich: Gegner (auch sterbende),
+// entwurzelte Bäume, Kugeln, ... Der Spieler bekommt seinen Rückstoß
+// in player_explosion_damage_system.
+#[allow(clippy::type_complexity)]
+fn explosion_impulse_system(
+    mut commands: Commands,
+    mut explosion_events: EventReader<ExplosionEvent>,
+    rapier_context: ReadRapierContext,
+    mut bodies: Query<(&GlobalTransform, Option<&mut ExternalImpulse>), Without<Player>>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+
+    for explosion in explosion_events.read() {
+        // Treffer sind Collider; mehrere Collider gehören evtl. zu einem Körper
+        let mut hit_bodies = Vec::new();
+        rapier_context.intersections_with_shape(
+            explosion.position,
+            Quat::IDENTITY,
+            &Collider::ball(explosion.affected_zone_radius),
+            QueryFilter::only_dynamic()
+                .exclude_sensors()
+                .exclude_rigid_body(explosion.source),
+            |collider| {
+                let body = rapier_context
+                    .colliders
+                    .collider_parent(rapier_context.rigidbody_set, collider)
+                    .unwrap_or(collider);
+                if !hit_bodies.contains(&body) {
+                    hit_bodies.push(body);
+                }
+                true
             },
-            Mesh3d(meshes.add(Sphere::new(rng.random_range(0.08..0.18)))),
-            MeshMaterial3d(mat_handle),
-            Transform::from_translation(explosion_pos),
-            Visibility::Visible,
-        ));
-        if i == 0 {
-            // Sound abspielen, append it to the first particle
-            e.insert((
-                AudioPlayer::new(explosion_sound.0.clone()),
-                PlaybackSettings::ONCE.with_spatial(true),
-            ));
+        );
+
+        for body in hit_bodies {
+            let Ok((transform, impulse)) = bodies.get_mut(body) else {
+                continue;
+            };
+            let Some(mass) = rapier_context
+                .rigidbody_set
+                .entity2body()
+                .get(&body)
+                .and_then(|handle| rapier_context.rigidbody_set.bodies.get(*handle))
+                .map(|rigid_body| rigid_body.mass())
+            else {
+                continue;
+            };
+
+            let offset = transform.translation() - explosion.position;
+            let falloff = explosion_falloff(
+                offset.length(),
+                explosion.dead_zone_radius,
+                explosion.affected_zone_radius,
+            );
+            if falloff <= 0.0 {
+                continue;
+            }
+            let dir = offset.with_y(0.0).normalize_or_zero();
+            let strength = BLAST_SPEED * falloff * mass;
+            // Kippen vom Explosionszentrum weg
+            let tumble_axis = Vec3::Y.cross(dir);
+            let blast = ExternalImpulse {
+                impulse: dir * strength + Vec3::Y * (strength * 0.8),
+                torque_impulse: tumble_axis * strength * BLAST_TORQUE,
+            };
+
+            match impulse {
+                Some(mut impulse) => {
+                    impulse.impulse += blast.impulse;
+                    impulse.torque_impulse += blast.torque_impulse;
+                }
+                None => {
+                    commands.entity(body).try_insert(blast);
+                }
+            }
         }
     }
+}
 
-    // Schaden und Impuls für Gegner im Umkreis
-    let mut killed = 0;
-    for (other_entity, other_transform, mut impulse) in all_enemies.iter_mut() {
-        let dist = (other_transform.translation - explosion_pos).length();
-        if dist <= 3.0 {
-            commands.entity(other_entity).insert(DelayedDeath {
-                timer: Timer::from_seconds(2.0, TimerMode::Once),
-            });
-            killed += 1;
+// Von einer nahen Explosion erfasst: detoniert nach kurzer Verzögerung
+#[derive(Component)]
+struct ChainDetonation {
+    timer: Timer,
+}
+
+// Kettenreaktion: Bäume im Wirkungsbereich werden entwurzelt,
+// andere ausstehende Explosionen in der Todeszone gezündet
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn explosion_chain_reaction_system(
+    mut commands: Commands,
+    mut explosion_events: EventReader<ExplosionEvent>,
+    tree_query: Query<(Entity, &Children, &Transform, &TreeRoot), Without<PendingExplosion>>,
+    pending_query: Query<
+        (Entity, &Transform, Option<&ExplosionChain>),
+        (With<PendingExplosion>, Without<ChainDetonation>),
+    >,
+    mut particle_events: EventWriter<ParticleEvent>,
+    mut uproot_events: EventWriter<TreeUprootedEvent>,
+    stake_sound: Res<StakeSound>,
+    mut chain_tracker: ResMut<ChainTracker>,
+    mut score_events: EventWriter<ScoreEvent>,
+    time: Res<Time>,
+) {
+    // Mehrere Explosionen im selben Frame dürfen nichts doppelt auslösen
+    let mut handled = Vec::new();
+
+    for explosion in explosion_events.read() {
+        let chain = explosion.chain.map(|chain| chain.next_link());
+
+        let mut trees: Vec<(Entity, f32)> = tree_query
+            .iter()
+            .map(|(entity, _, transform, _)| {
+                (entity, transform.translation.distance(explosion.position))
+            })
+            .filter(|(entity, dist)| {
+                *dist <= explosion.affected_zone_radius && !handled.contains(entity)
+            })
+            .collect();
+        trees.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        for (tree_entity, _) in trees.into_iter().take(MAX_UPROOTS_PER_EXPLOSION) {
+            let uprooted = maybe_uproot_tree(
+                &mut commands,
+                tree_entity,
+                &tree_query,
+                &stake_sound,
+                &mut particle_events,
+                &mut uproot_events,
+            );
+            if let Some(tree_transform) = uprooted {
+                handled.push(tree_entity);
+                link_uprooted_tree(
+                    &mut commands,
+                    &mut chain_tracker,
+                    &mut score_events,
+                    tree_entity,
+                    tree_transform.translation,
+                    chain,
+                    time.elapsed_secs(),
+                );
+            }
         }
-        if dist <= 10.0 {
-            let dir = (other_transform.translation - explosion_pos)
-                .with_y(0.0)
-                .normalize_or_zero();
-            let strength = 20.0 * (1.0 - (dist / 10.0)).clamp(0.0, 1.0);
-            impulse.impulse += dir * strength + Vec3::Y * (strength * 0.8);
+
+        for (entity, transform, own_chain) in pending_query.iter() {
+            let dist = transform.translation.distance(explosion.position);
+            if entity == explosion.source
+                || dist > explosion.dead_zone_radius
+                || handled.contains(&entity)
+            {
+                continue;
+            }
+            handled.push(entity);
+            let mut entity_commands = commands.entity(entity);
+            entity_commands.try_insert(ChainDetonation {
+                timer: Timer::from_seconds(
+                    CHAIN_DELAY_SECS + dist * CHAIN_DELAY_PER_METER,
+                    TimerMode::Once,
+                ),
+            });
+            // Eigene Kette behalten, sonst an die auslösende anhängen
+            if own_chain.is_none()
+                && let Some(chain) = chain
+            {
+                chain_tracker.register(entity, chain, time.elapsed_secs());
+                entity_commands.try_insert(chain);
+            }
         }
     }
-    player_health.value += killed as f32 * 2.0;
-    if killed > 0 {
-        println!(
-            "{} Gegner explodiert! Spieler bekommt {} Leben zurück.",
-            killed,
-            killed * 2
+}
+
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn chain_detonation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &PendingExplosion,
+        Option<&ExplosionChain>,
+        &mut ChainDetonation,
+    )>,
+    mut explosion_events: EventWriter<ExplosionEvent>,
+    mut particle_events: EventWriter<ParticleEvent>,
+    explosion_sound: Res<ExplosionSound>,
+) {
+    for (entity, transform, explosion, chain, mut detonation) in query.iter_mut() {
+        detonation.timer.tick(time.delta());
+        if !detonation.timer.finished() {
+            continue;
+        }
+        explode_pending_entity(
+            &mut commands,
+            entity,
+            transform.translation,
+            explosion,
+            chain.copied(),
+            &mut explosion_events,
+            &mut particle_events,
+            &explosion_sound,
         );
     }
+}
 
-    // Entity entfernen
-    commands.entity(entity).despawn();
+// Entwurzelter Baum wird zum nächsten Glied der Kette (oder beginnt eine neue)
+fn link_uprooted_tree(
+    commands: &mut Commands,
+    chain_tracker: &mut ResMut<ChainTracker>,
+    score_events: &mut EventWriter<ScoreEvent>,
+    tree_entity: Entity,
+    position: Vec3,
+    chain: Option<ExplosionChain>,
+    now: f32,
+) {
+    if let Some(chain) = chain {
+        chain_tracker.register(tree_entity, chain, now);
+        commands.entity(tree_entity).insert(chain);
+    }
+    // Von Gegnern ausgelöste Ketten bringen keine Punkte
+    if chain.is_some_and(|chain| chain.instigator == Faction::Enemy) {
+        return;
+    }
+    score_events.write(ScoreEvent {
+        kind: ScoreKind::TreeUprooted,
+        chain,
+        position,
+    });
 }
 
+// Treffer nach Seite und Friendly-Fire-Regeln; der Schaden kommt vom Projektil
+#[allow(clippy::type_complexity)]
 pub fn bullet_collision_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
-    player: Single<(Entity, &mut Health), With<Player>>,
-    mut enemy_query: Query<(Entity, &mut ExternalImpulse), With<Enemy>>,
-    bullet_query: Query<(Entity, &Velocity), With<Bullet>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    friendly_fire: Res<FriendlyFireSettings>,
+    player_entity: Single<Entity, With<Player>>,
+    mut enemy_query: Query<&mut ExternalImpulse, (With<Enemy>, Without<PendingExplosion>)>,
+    bullet_query: Query<(Entity, &Bullet, &Transform, &Velocity), Without<SpentProjectile>>,
 ) {
-    let (player_entity, mut health) = player.into_inner();
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
             // Finde die Bullet und ihre Velocity
-            let ((bullet_entity, bullet_velocity), other) = if let Ok(b) = bullet_query.get(*e1) {
-                (b, e2)
-            } else if let Ok(b) = bullet_query.get(*e2) {
-                (b, e1)
+            let ((bullet_entity, bullet, bullet_transform, bullet_velocity), other) =
+                if let Ok(b) = bullet_query.get(*e1) {
+                    (b, *e2)
+                } else if let Ok(b) = bullet_query.get(*e2) {
+                    (b, *e1)
+                } else {
+                    continue;
+                };
+
+            let target_faction = if other == *player_entity {
+                Faction::Player
+            } else if enemy_query.contains(other) {
+                Faction::Enemy
             } else {
                 continue;
             };
-
-            // Prüfe, ob der andere ein Spieler ist
-            if player_entity == *other {
-                health.value -= 1.0;
-                println!("Spieler getroffen! Leben: {}", health.value);
+            let scale = friendly_fire.damage_scale(bullet, other, target_faction);
+            if scale <= 0.0 {
                 continue;
             }
 
-            // Prüfe, ob der andere ein Gegner ist
-            if let Ok((enemy_entity, mut impulse)) = enemy_query.get_mut(*other) {
+            if let Ok(mut impulse) = enemy_query.get_mut(other) {
                 let dir = bullet_velocity.linvel.normalize_or_zero();
                 let impulse_vec = dir * 60.0 + Vec3::Y * 6.0;
-                impulse.impulse += impulse_vec;
+                impulse.impulse += impulse_vec * scale;
+            }
+
+            // Als Quelle zählt der Schütze, nicht die (wiederverwendete) Kugel
+            damage_events.write(DamageEvent {
+                source: bullet.owner,
+                target: other,
+                amount: bullet.damage * scale,
+                kind: bullet.kind,
+                position: bullet_transform.translation,
+            });
+            commands.entity(bullet_entity).try_insert(SpentProjectile);
+        }
+    }
+}
 
-                // Explosion vormerken mit Suppression
-                commands.entity(enemy_entity).insert((
+fn player_enemy_ramming_system(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    player: Single<(Entity, &Transform, &Velocity), With<Player>>,
+    enemy_query: Query<&Velocity, (With<Enemy>, Without<PendingExplosion>)>,
+) {
+    let (player_entity, player_transform, player_velocity) = player.into_inner();
+
+    for event in collision_events.read() {
+        if let CollisionEvent::Started(e1, e2, _) = event {
+            let other = if player_entity == *e1 {
+                e2
+            } else if player_entity == *e2 {
+                e1
+            } else {
+                continue;
+            };
+            let Ok(enemy_velocity) = enemy_query.get(*other) else {
+                continue;
+            };
+
+            // Schaden nur bei ordentlichem Aufprall
+            let impact_speed = (player_velocity.linvel - enemy_velocity.linvel).length();
+            if impact_speed < RAMMING_MIN_SPEED {
+                continue;
+            }
+            let damage = (impact_speed - RAMMING_MIN_SPEED) * RAMMING_DAMAGE_PER_SPEED;
+            damage_events.write(DamageEvent {
+                source: Some(player_entity),
+                target: *other,
+                amount: damage,
+                kind: DamageKind::Ramming,
+                position: player_transform.translation,
+            });
+        }
+    }
+}
+
+// Gegner sterben je nach Todesart: Explosionsopfer verschwinden verzögert,
+// alle anderen werden selbst zur Explosion
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn enemy_death_system(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    enemy_query: Query<
+        (),
+        (
+            With<Enemy>,
+            Without<PendingExplosion>,
+            Without<DelayedDeath>,
+        ),
+    >,
+    player_entity: Single<Entity, With<Player>>,
+    mut heal_events: EventWriter<HealEvent>,
+    mut chain_tracker: ResMut<ChainTracker>,
+    friendly_fire: Res<FriendlyFireSettings>,
+    time: Res<Time>,
+) {
+    for event in death_events.read() {
+        if !enemy_query.contains(event.target) {
+            continue;
+        }
+        match event.kind {
+            DamageKind::Explosion => {
+                commands.entity(event.target).try_insert(DelayedDeath {
+                    timer: Timer::from_seconds(2.0, TimerMode::Once),
+                });
+                // Spieler bekommt für jeden von ihm weggesprengten Gegner Leben zurück
+                let by_player = event
+                    .source
+                    .and_then(|source| chain_tracker.chain_of(source))
+                    .is_some_and(|chain| chain.instigator == Faction::Player);
+                if by_player {
+                    heal_events.write(HealEvent {
+                        source: Some(event.target),
+                        target: *player_entity,
+                        amount: 2.0,
+                        position: event.position,
+                    });
+                }
+            }
+            DamageKind::Bullet | DamageKind::Ramming | DamageKind::Melee => {
+                let by_player = event.source == Some(*player_entity);
+                if !by_player && !friendly_fire.enemy_kills_detonate {
+                    // Von anderen Gegnern erschossen: ohne Explosion verschwinden
+                    commands.entity(event.target).try_insert(DelayedDeath {
+                        timer: Timer::from_seconds(2.0, TimerMode::Once),
+                    });
+                    continue;
+                }
+                // Anfang einer neuen Kettenreaktion
+                let instigator = if by_player {
+                    Faction::Player
+                } else {
+                    Faction::Enemy
+                };
+                let chain = chain_tracker.start_chain(instigator);
+                chain_tracker.register(event.target, chain, time.elapsed_secs());
+                commands.entity(event.target).try_insert((
                     PendingExplosion::default(),
                     PendingExplosionSuppressed::default(),
+                    chain,
                 ));
-
-                commands.entity(bullet_entity).despawn();
-                continue;
             }
         }
     }
@@ -364,20 +862,49 @@ pub struct ExplosionPlugin;
 
 impl Plugin for ExplosionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup).add_systems(
-            Update,
-            (
-                tree_explosion_system,
-                explosion_particle_system,
-                player_tree_collision_system,
-                pending_explosion_system,
-                ground_explosion_system,
-                delayed_death_system,
-                bullet_collision_system,
-                pending_explosion_suppression_system,
-            )
-                .run_if(in_state(AppState::Running)),
-        );
+        app.add_event::<ExplosionEvent>()
+            .init_resource::<ExplosionSettings>()
+            .add_systems(Startup, setup)
+            .add_systems(OnExit(AppState::GameOver), clear_pending_explosions)
+            .add_systems(
+                Update,
+                (
+                    // Nacheinander, damit die Despawns der vorigen Systeme schon gelten
+                    // und keine Entity zweimal explodiert
+                    (
+                        tree_explosion_system,
+                        ground_explosion_system,
+                        chain_detonation_system,
+                    )
+                        .chain(),
+                    player_tree_collision_system,
+                    charge_visual_setup_system,
+                    pending_explosion_system.after(charge_visual_setup_system),
+                    charge_visual_restore_system,
+                    delayed_death_system,
+                    bullet_collision_system,
+                    player_enemy_ramming_system,
+                    enemy_death_system,
+                    pending_explosion_suppression_system,
+                    explosion_damage_system,
+                    explosion_impulse_system,
+                    player_explosion_damage_system,
+                    explosion_camera_shake_system,
+                    explosion_chain_reaction_system,
+                )
+                    .run_if(in_state(AppState::Running)),
+            );
+    }
+}
+
+// Beim Neustart alle noch ausstehenden Explosionen (z.B. entwurzelte Bäume) entfernen
+#[allow(clippy::type_complexity)]
+fn clear_pending_explosions(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<PendingExplosion>, With<DelayedDeath>)>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
     }
 }
 
@@ -405,7 +932,13 @@ fn tree_explosion_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     pending_explosions: Query<
-        Entity,
+        (
+            Entity,
+            &Transform,
+            &PendingExplosion,
+            Option<&ExplosionChain>,
+            Has<Enemy>,
+        ),
         (With<PendingExplosion>, Without<PendingExplosionSuppressed>),
     >,
     tree_query: Query<(Entity, &Children, &Transform, &TreeRoot), Without<PendingExplosion>>,
@@ -413,58 +946,67 @@ fn tree_explosion_system(
         (Entity, &ChildOf),
         (With<TreeCollider>, Without<PendingExplosion>),
     >,
-    mut all_enemies: Query<
-        (Entity, &Transform, &mut ExternalImpulse),
-        (
-            With<Enemy>,
-            Without<DelayedDeath>,
-            Without<PendingExplosion>,
-        ),
-    >,
-    mut player_health: Single<&mut Health, With<Player>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut explosion_events: EventWriter<ExplosionEvent>,
+    mut particle_events: EventWriter<ParticleEvent>,
+    mut uproot_events: EventWriter<TreeUprootedEvent>,
     explosion_sound: Res<ExplosionSound>,
-    root_assets: Res<RootParticleAssets>,
     stake_sound: Res<StakeSound>,
+    mut chain_tracker: ResMut<ChainTracker>,
+    mut score_events: EventWriter<ScoreEvent>,
+    time: Res<Time>,
 ) {
+    let mut detonated = HashSet::new();
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
             // Prüfe, ob eine Seite PendingExplosion und die andere ein Baum ohne PendingExplosion ist
-            let (pending_entity, tree_child_entity) = if pending_explosions.contains(*e1) {
+            let (
+                (pending_entity, pending_transform, explosion, pending_chain, is_enemy),
+                tree_child_entity,
+            ) = if let Ok(pending) = pending_explosions.get(*e1) {
                 let Ok((_, ChildOf(parent))) = tree_colliders_query.get(*e2) else {
                     continue;
                 };
-                (*e1, *parent)
-            } else if pending_explosions.contains(*e2) {
+                (pending, *parent)
+            } else if let Ok(pending) = pending_explosions.get(*e2) {
                 let Ok((_, ChildOf(parent))) = tree_colliders_query.get(*e1) else {
                     continue;
                 };
-                (*e2, *parent)
+                (pending, *parent)
             } else {
                 continue;
             };
 
-            maybe_uproot_tree(
+            let uprooted = maybe_uproot_tree(
                 &mut commands,
                 tree_child_entity,
                 &tree_query,
-                &root_assets,
-                &mut meshes,
-                &mut materials,
                 &stake_sound,
+                &mut particle_events,
+                &mut uproot_events,
             );
+            if let Some(tree_transform) = uprooted {
+                // Der Baum wird zum nächsten Glied der Kette
+                link_uprooted_tree(
+                    &mut commands,
+                    &mut chain_tracker,
+                    &mut score_events,
+                    tree_child_entity,
+                    tree_transform.translation,
+                    pending_chain.map(ExplosionChain::next_link),
+                    time.elapsed_secs(),
+                );
+            }
 
-            // --- pending_entity explodieren lassen wie im explosion_system ---
-            if let Ok((_, transform, _)) = all_enemies.get(pending_entity) {
+            // Gegner explodieren beim Aufprall auf den Baum, Bäume erst am Boden
+            if is_enemy && detonated.insert(pending_entity) {
                 explode_pending_entity(
                     &mut commands,
                     pending_entity,
-                    transform.translation,
-                    &mut all_enemies,
-                    &mut player_health,
-                    &mut meshes,
-                    &mut materials,
+                    pending_transform.translation,
+                    explosion,
+                    pending_chain.copied(),
+                    &mut explosion_events,
+                    &mut particle_events,
                     &explosion_sound,
                 );
             }
@@ -483,10 +1025,12 @@ fn player_tree_collision_system(
         (Entity, &ChildOf),
         (With<TreeCollider>, Without<PendingExplosion>),
     >,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    root_assets: Res<RootParticleAssets>,
+    mut particle_events: EventWriter<ParticleEvent>,
+    mut uproot_events: EventWriter<TreeUprootedEvent>,
     stake_sound: Res<StakeSound>,
+    mut chain_tracker: ResMut<ChainTracker>,
+    mut score_events: EventWriter<ScoreEvent>,
+    time: Res<Time>,
 ) {
     let (player_entity, player_velocity, Player { speed, .. }) = player.into_inner();
 
@@ -513,15 +1057,27 @@ fn player_tree_collision_system(
                 continue;
             }
 
-            maybe_uproot_tree(
+            let uprooted = maybe_uproot_tree(
                 &mut commands,
                 tree_entity,
                 &tree_query,
-                &root_assets,
-                &mut meshes,
-                &mut materials,
                 &stake_sound,
+                &mut particle_events,
+                &mut uproot_events,
             );
+            if let Some(tree_transform) = uprooted {
+                // Vom Spieler umgerannt: neue Kettenreaktion
+                let chain = chain_tracker.start_chain(Faction::Player);
+                link_uprooted_tree(
+                    &mut commands,
+                    &mut chain_tracker,
+                    &mut score_events,
+                    tree_entity,
+                    tree_transform.translation,
+                    Some(chain),
+                    time.elapsed_secs(),
+                );
+            }
         }
     }
 }
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+
+use crate::AppState;
+
+use super::{health::DeathEvent, player::Player, score::Score};
+
+#[derive(Component)]
+struct GameOverRoot;
+
+#[derive(Component)]
+struct RestartButton;
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Todesbildschirm anzeigen, wenn GameOver-Status betreten wird
+            .add_systems(OnEnter(AppState::GameOver), show_game_over_screen)
+            // Beim Neustart wieder ausblenden (Reset übernehmen die jeweiligen Plugins)
+            .add_systems(OnExit(AppState::GameOver), hide_game_over_screen)
+            .add_systems(
+                Update,
+                (
+                    player_death_system.run_if(in_state(AppState::Running)),
+                    restart_button_system.run_if(in_state(AppState::GameOver)),
+                ),
+            );
+    }
+}
+
+// System: Wechselt in GameOver-State, sobald der Spieler stirbt
+fn player_death_system(
+    mut death_events: EventReader<DeathEvent>,
+    player_entity: Single<Entity, With<Player>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if !death_events
+        .read()
+        .any(|event| event.target == *player_entity)
+    {
+        return;
+    }
+    next_state.set(AppState::GameOver);
+    window.cursor_options.grab_mode = CursorGrabMode::None;
+    window.cursor_options.visible = true;
+    virtual_time.pause();
+}
+
+fn show_game_over_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    query: Query<Entity, With<GameOverRoot>>,
+    score: Res<Score>,
+) {
+    if !query.is_empty() {
+        return;
+    }
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.spawn((
+        GameOverRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(0.0),
+            left: Val::Percent(0.0),
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(30.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.3, 0.0, 0.0, 0.7)),
+        children![
+            (
+                Node::default(),
+                Text("Game Over".to_string()),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 72.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE)
+            ),
+            (
+                Node::default(),
+                Text(format!(
+                    "Punkte: {}\nGegner: {}  Bäume: {}  Längste Kette: {}",
+                    score.total, score.kills, score.trees, score.longest_chain
+                )),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextLayout::new_with_justify(JustifyText::Center),
+                TextColor(Color::WHITE)
+            ),
+            (
+                Node { ..default() },
+                Button,
+                RestartButton,
+                BackgroundColor(Color::srgba(0.5, 0.5, 0.5, 1.0)),
+                children![(
+                    Node::default(),
+                    Text("Neustart".to_string()),
+                    TextFont {
+                        font,
+                        font_size: 40.0,
+                        ..default()
+                    },
+                    TextColor(Color::BLACK)
+                )]
+            )
+        ],
+    ));
+}
+
+fn hide_game_over_screen(mut commands: Commands, query: Query<Entity, With<GameOverRoot>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn restart_button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<RestartButton>),
+    >,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    for (interaction, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(AppState::Running);
+                window.cursor_options.grab_mode = CursorGrabMode::Locked;
+                window.cursor_options.visible = false;
+                virtual_time.unpause();
+            }
+            Interaction::Hovered => {
+                *color = Color::srgba(0.2, 0.2, 0.2, 1.0).into();
+            }
+            Interaction::None => {
+                *color = Color::srgba(0.5, 0.5, 0.5, 1.0).into();
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{AppState, assets::AssetLoadingState};
+
+use super::{
+    asset_cache::GameAssetCache, explosion::ExplosionEvent, level::LevelEntity,
+    tree::TreeUprootedEvent,
+};
+
+pub struct GroundMarkPlugin;
+
+impl Plugin for GroundMarkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GroundMarks>()
+            .add_systems(OnExit(AppState::GameOver), clear_ground_marks)
+            // Levelwechsel: sonst zeigt die Deque auf bereits entfernte Spuren
+            .add_systems(OnEnter(AssetLoadingState::Loading), clear_ground_marks)
+            .add_systems(
+                Update,
+                (
+                    explosion_mark_system,
+                    root_hole_mark_system,
+                    ground_mark_fade_system,
+                )
+                    .run_if(in_state(AppState::Running)),
+            );
+    }
+}
+
+// Ältere Spuren verschwinden, sobald es mehr werden
+const MAX_GROUND_MARKS: usize = 40;
+const MARK_LIFETIME_SECS: f32 = 45.0;
+// Im letzten Teil der Lebenszeit wird ausgeblendet
+const MARK_FADE_FRACTION: f32 = 0.3;
+// Knapp über der Bodenfläche (Ground liegt bei y = -0.05)
+const MARK_HEIGHT: f32 = -0.045;
+// Höher liegende Explosionen hinterlassen keine Spur
+const MAX_MARK_EXPLOSION_HEIGHT: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroundMarkKind {
+    Scorch,   // Brandfleck einer Explosion
+    RootHole, // Loch, wo ein Baum entwurzelt wurde
+}
+
+impl GroundMarkKind {
+    fn color(&self) -> Color {
+        match self {
+            GroundMarkKind::Scorch => Color::srgba(0.05, 0.04, 0.03, 0.85),
+            GroundMarkKind::RootHole => Color::srgba(0.18, 0.11, 0.05, 0.95),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct GroundMark {
+    pub kind: GroundMarkKind,
+    pub lifetime: Timer,
+    material: Handle<StandardMaterial>,
+}
+
+// Reihenfolge der Spuren, älteste zuerst
+#[derive(Resource, Default)]
+struct GroundMarks(VecDeque<Entity>);
+
+fn spawn_ground_mark(
+    commands: &mut Commands,
+    marks: &mut GroundMarks,
+    cache: &GameAssetCache,
+    materials: &mut Assets<StandardMaterial>,
+    kind: GroundMarkKind,
+    position: Vec3,
+    radius: f32,
+) {
+    let material = materials.add(StandardMaterial {
+        base_color: kind.color(),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    // Eigenes Material, weil jede Spur für sich ausgeblendet wird
+    // Jede Spur etwas höher, damit sich überlappende nicht flackern
+    let height = MARK_HEIGHT + (marks.0.len() as f32) * 0.0005;
+    let angle = rand::rng().random_range(0.0..std::f32::consts::TAU);
+    let entity = commands
+        .spawn((
+            GroundMark {
+                kind,
+                lifetime: Timer::from_seconds(MARK_LIFETIME_SECS, TimerMode::Once),
+                material: material.clone(),
+            },
+            LevelEntity,
+            Mesh3d(cache.unit_disc.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(position.with_y(height))
+                .with_rotation(
+                    Quat::from_rotation_y(angle)
+                        * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+                )
+                .with_scale(Vec3::new(radius, radius * 0.85, 1.0)),
+            Visibility::Visible,
+        ))
+        .id();
+
+    marks.0.push_back(entity);
+    while marks.0.len() > MAX_GROUND_MARKS {
+        if let Some(oldest) = marks.0.pop_front() {
+            commands.entity(oldest).try_despawn();
+        }
+    }
+}
+
+fn explosion_mark_system(
+    mut commands: Commands,
+    mut explosion_events: EventReader<ExplosionEvent>,
+    mut marks: ResMut<GroundMarks>,
+    cache: Res<GameAssetCache>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for explosion in explosion_events.read() {
+        if explosion.position.y > MAX_MARK_EXPLOSION_HEIGHT {
+            continue;
+        }
+        spawn_ground_mark(
+            &mut commands,
+            &mut marks,
+            &cache,
+            &mut materials,
+            GroundMarkKind::Scorch,
+            explosion.position,
+            explosion.dead_zone_radius,
+        );
+    }
+}
+
+fn root_hole_mark_system(
+    mut commands: Commands,
+    mut uproot_events: EventReader<TreeUprootedEvent>,
+    mut marks: ResMut<GroundMarks>,
+    cache: Res<GameAssetCache>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for event in uproot_events.read() {
+        spawn_ground_mark(
+            &mut commands,
+            &mut marks,
+            &cache,
+            &mut materials,
+            GroundMarkKind::RootHole,
+            event.position,
+            event.trunk_radius * 2.5,
+        );
+    }
+}
+
+fn ground_mark_fade_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut marks: ResMut<GroundMarks>,
+    mut query: Query<(Entity, &mut GroundMark)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, mut mark) in query.iter_mut() {
+        mark.lifetime.tick(time.delta());
+        if mark.lifetime.finished() {
+            commands.entity(entity).despawn();
+            marks.0.retain(|e| *e != entity);
+            continue;
+        }
+
+        let remaining = 1.0 - mark.lifetime.fraction();
+        if remaining < MARK_FADE_FRACTION
+            && let Some(material) = materials.get_mut(&mark.material)
+        {
+            let alpha = mark.kind.color().alpha() * remaining / MARK_FADE_FRACTION;
+            material.base_color.set_alpha(alpha);
+        }
+    }
+}
+
+fn clear_ground_marks(mut commands: Commands, mut marks: ResMut<GroundMarks>) {
+    for entity in marks.0.drain(..) {
+        commands.entity(entity).try_despawn();
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{AppState, Ground};
+
+use super::{level::Wall, tree::TreeCollider};
+
+pub struct GroundedPlugin;
+
+impl Plugin for GroundedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                ground_detection_system,
+                self_righting_system.after(ground_detection_system),
+            )
+                .run_if(in_state(AppState::Running)),
+        );
+    }
+}
+
+// Wie weit unter der Unterseite noch Boden erkannt wird
+const GROUND_MARGIN: f32 = 0.1;
+// Ab dieser Neigung (Skalarprodukt mit der Y-Achse) gilt ein Körper als aufrecht
+pub const UPRIGHT_THRESHOLD: f32 = 0.8;
+
+// Bodenkontakt eines quaderförmigen Körpers, per Shape-Cast nach unten ermittelt.
+// Gilt auch für gekippte Körper und unebenen Untergrund.
+#[derive(Component, Debug)]
+pub struct Grounded {
+    pub half_extents: Vec3, // halbe Kantenlängen des Colliders
+    pub on_ground: bool,
+    pub normal: Vec3,
+}
+
+impl Grounded {
+    pub fn new(half_extents: Vec3) -> Self {
+        Grounded {
+            half_extents,
+            on_ground: false,
+            normal: Vec3::Y,
+        }
+    }
+}
+
+// 1.0 = aufrecht, 0.0 = auf der Seite, -1.0 = kopfüber
+pub fn uprightness(rotation: Quat) -> f32 {
+    (rotation * Vec3::Y).y
+}
+
+// Halbe Höhe der weltachsenparallelen Bounding-Box eines gedrehten Quaders
+pub fn vertical_half_extent(rotation: Quat, half_extents: Vec3) -> f32 {
+    let matrix = Mat3::from_quat(rotation);
+    matrix.x_axis.y.abs() * half_extents.x
+        + matrix.y_axis.y.abs() * half_extents.y
+        + matrix.z_axis.y.abs() * half_extents.z
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn ground_detection_system(
+    rapier_context: ReadRapierContext,
+    mut query: Query<(Entity, &Transform, &mut Grounded)>,
+    ground_query: Query<(), Or<(With<Ground>, With<TreeCollider>, With<Wall>)>>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+    let is_ground = |e: Entity| ground_query.contains(e);
+
+    for (entity, transform, mut grounded) in query.iter_mut() {
+        // Flache Platte mit der Grundfläche des Körpers, nur um die Hochachse gedreht
+        let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        let footprint = grounded.half_extents.xz().min_element() * 0.9;
+        let shape = Collider::cuboid(footprint, 0.05, footprint);
+        let depth = vertical_half_extent(transform.rotation, grounded.half_extents);
+
+        let filter = QueryFilter::default()
+            .exclude_rigid_body(entity)
+            .exclude_sensors()
+            .predicate(&is_ground);
+
+        let hit = rapier_context.cast_shape(
+            transform.translation,
+            Quat::from_rotation_y(yaw),
+            -Vec3::Y,
+            &shape,
+            ShapeCastOptions {
+                max_time_of_impact: depth - 0.05 + GROUND_MARGIN,
+                compute_impact_geometry_on_penetration: true,
+                ..default()
+            },
+            filter,
+        );
+
+        grounded.on_ground = hit.is_some();
+        grounded.normal = hit
+            .and_then(|(_, hit)| hit.details)
+            .map_or(Vec3::Y, |details| details.normal1);
+    }
+}
+
+// Richtet umgekippte Körper nach kurzer Zeit mit einem Hüpfer und Drehimpuls wieder auf
+#[derive(Component, Debug)]
+pub struct SelfRighting {
+    pub timer: Timer,
+    pub hop_speed: f32,  // Aufwärtsgeschwindigkeit des Hüpfers
+    pub spin_speed: f32, // Winkelgeschwindigkeit beim Aufrichten
+}
+
+impl Default for SelfRighting {
+    fn default() -> Self {
+        SelfRighting {
+            timer: Timer::from_seconds(1.0, TimerMode::Once),
+            hop_speed: 4.0,
+            spin_speed: 6.0,
+        }
+    }
+}
+
+fn self_righting_system(
+    time: Res<Time>,
+    mut query: Query<(
+        &Transform,
+        &Grounded,
+        &ReadMassProperties,
+        &mut SelfRighting,
+        &mut ExternalImpulse,
+    )>,
+) {
+    for (transform, grounded, mass, mut righting, mut impulse) in query.iter_mut() {
+        let up = transform.rotation * Vec3::Y;
+        if !grounded.on_ground || up.y >= UPRIGHT_THRESHOLD {
+            righting.timer.reset();
+            continue;
+        }
+
+        righting.timer.tick(time.delta());
+        if !righting.timer.finished() {
+            continue;
+        }
+        righting.timer.reset();
+
+        // Drehachse, die die lokale Hochachse zur Welt-Y-Achse dreht
+        let axis = up.cross(Vec3::Y).normalize_or(transform.rotation * Vec3::X);
+        let mass = mass.get();
+        impulse.impulse += Vec3::Y * mass.mass * righting.hop_speed;
+        impulse.torque_impulse += axis * mass.principal_inertia.max_element() * righting.spin_speed;
+    }
+}
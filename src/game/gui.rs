@@ -1,5 +1,7 @@
 use crate::game::health::Health;
+use crate::game::pickup::PowerUps;
 use crate::game::player::Player;
+use crate::game::weapon::PlayerWeapon;
 use bevy::prelude::*;
 use bevy::text::FontStyle;
 
@@ -8,32 +10,51 @@ pub struct GuiPlugin;
 impl Plugin for GuiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup_gui)
-            .add_systems(Update, update_health_text);
+            .add_systems(Update, (update_health_text, update_status_text));
     }
 }
 
 #[derive(Component)]
 struct HealthText;
 
+// Munition und aktive Power-Ups
+#[derive(Component)]
+struct StatusText;
+
 fn setup_gui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
     commands.spawn((
         Node {
             position_type: PositionType::Absolute,
             top: Val::Px(20.0),
             left: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
             ..default()
         },
-        children![(
-            HealthText,
-            Node::default(),
-            Text { ..default() },
-            TextFont {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::BLACK)
-        )],
+        children![
+            (
+                HealthText,
+                Node::default(),
+                Text { ..default() },
+                TextFont {
+                    font: font.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(Color::BLACK)
+            ),
+            (
+                StatusText,
+                Node::default(),
+                Text { ..default() },
+                TextFont {
+                    font,
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::BLACK)
+            )
+        ],
     ));
 }
 
@@ -43,3 +64,21 @@ fn update_health_text(
 ) {
     text.0 = format!("Leben: {:.0}", player.value);
 }
+
+fn update_status_text(
+    player: Single<(&PlayerWeapon, &PowerUps), With<Player>>,
+    mut text: Single<&mut Text, With<StatusText>>,
+) {
+    let (weapon, power_ups) = player.into_inner();
+    let mut status = format!("Munition: {}", weapon.ammo);
+    if !power_ups.rapid_fire.finished() {
+        status += &format!(
+            "  Schnellfeuer {:.0}s",
+            power_ups.rapid_fire.remaining_secs()
+        );
+    }
+    if !power_ups.speed_boost.finished() {
+        status += &format!("  Tempo {:.0}s", power_ups.speed_boost.remaining_secs());
+    }
+    text.0 = status;
+}
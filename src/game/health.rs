@@ -3,3 +3,92 @@ use bevy::prelude::*;
 pub struct Health {
     pub value: f32,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DamageKind {
+    Bullet,
+    Explosion,
+    Ramming,
+    Melee,
+}
+
+// Seite im Kampf, für Friendly Fire und die Zuordnung von Kills
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Faction {
+    Player,
+    Enemy,
+}
+
+// Schaden wird nie direkt an Health abgezogen, sondern immer über dieses Event
+#[derive(Event, Clone, Debug)]
+pub struct DamageEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: f32,
+    pub kind: DamageKind,
+    pub position: Vec3,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct HealEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: f32,
+    pub position: Vec3,
+}
+
+// Wird genau einmal geschickt, wenn Health eines Ziels auf 0 oder darunter fällt
+#[derive(Event, Clone, Debug)]
+pub struct DeathEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub kind: DamageKind,
+    pub position: Vec3,
+}
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .add_event::<HealEvent>()
+            .add_event::<DeathEvent>()
+            // Alles, was im Update geschickt wurde, am Ende des Frames anwenden
+            .add_systems(PostUpdate, apply_health_events);
+    }
+}
+
+fn apply_health_events(
+    mut damage_events: EventReader<DamageEvent>,
+    mut heal_events: EventReader<HealEvent>,
+    mut death_events: EventWriter<DeathEvent>,
+    mut query: Query<&mut Health>,
+) {
+    for event in heal_events.read() {
+        let Ok(mut health) = query.get_mut(event.target) else {
+            continue;
+        };
+        // Tote werden nicht wiederbelebt
+        if health.value > 0.0 {
+            health.value += event.amount;
+        }
+    }
+
+    for event in damage_events.read() {
+        let Ok(mut health) = query.get_mut(event.target) else {
+            continue;
+        };
+        if health.value <= 0.0 {
+            continue;
+        }
+        health.value -= event.amount;
+        if health.value <= 0.0 {
+            death_events.write(DeathEvent {
+                source: event.source,
+                target: event.target,
+                kind: event.kind,
+                position: event.position,
+            });
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use bevy::{
+    image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor},
+    prelude::*,
+    render::mesh::VertexAttributeValues,
+};
+use bevy_common_assets::json::JsonAssetPlugin;
+use bevy_rapier3d::prelude::*;
+
+use crate::{
+    Ground,
+    assets::{AssetLoadingState, levels::Level},
+};
+
+use super::{enemy::Enemy, explosion::PendingExplosion};
+
+const FIRST_LEVEL: &str = "levels/level_1.level.json";
+
+#[derive(Resource)]
+pub struct CurrentLevel {
+    pub handle: Handle<Level>,
+}
+
+impl CurrentLevel {
+    pub fn get<'a>(&self, levels: &'a Assets<Level>) -> Option<&'a Level> {
+        levels.get(&self.handle)
+    }
+}
+
+// Wechselt zum Level unter dem angegebenen Asset-Pfad
+#[derive(Event)]
+pub struct ChangeLevel {
+    pub path: String,
+}
+
+// Alles, was beim Levelwechsel entfernt werden muss (Boden, Wände, Bäume).
+// Gegner und ausstehende Explosionen werden zusätzlich entfernt, Projektile
+// und Partikel gehen in OnEnter(AssetLoadingState::Loading) zurück in ihre Pools.
+#[derive(Component)]
+pub struct LevelEntity;
+
+// Begrenzungswand des Levels
+#[derive(Component)]
+pub struct Wall;
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(JsonAssetPlugin::<Level>::new(&["level.json"]))
+            .init_state::<AssetLoadingState>()
+            .add_event::<ChangeLevel>()
+            .add_systems(Startup, load_first_level)
+            .add_systems(OnEnter(AssetLoadingState::Done), spawn_level_world)
+            .add_systems(Update, change_level_system);
+    }
+}
+
+fn load_first_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CurrentLevel {
+        handle: asset_server.load(FIRST_LEVEL),
+    });
+}
+
+#[allow(clippy::type_complexity)]
+fn change_level_system(
+    mut commands: Commands,
+    mut change_level: EventReader<ChangeLevel>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<AssetLoadingState>>,
+    asset_server: Res<AssetServer>,
+    level_entities: Query<Entity, Or<(With<LevelEntity>, With<Enemy>, With<PendingExplosion>)>>,
+) {
+    let Some(event) = change_level.read().last() else {
+        return;
+    };
+
+    // Alte Welt abbauen, neues Level laden; gespawnt wird in OnEnter(Done)
+    for entity in level_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+    current_level.handle = asset_server.load(&event.path);
+    next_state.set(AssetLoadingState::Loading);
+}
+
+fn load_repeating_texture(asset_server: &AssetServer, path: &str) -> Handle<Image> {
+    asset_server.load_with_settings(path.to_string(), |s: &mut _| {
+        *s = ImageLoaderSettings {
+            sampler: ImageSampler::Descriptor(ImageSamplerDescriptor {
+                address_mode_u: ImageAddressMode::Repeat,
+                address_mode_v: ImageAddressMode::Repeat,
+                ..default()
+            }),
+            ..default()
+        }
+    })
+}
+
+fn spawn_level_world(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = current_level.get(&levels) else {
+        return;
+    };
+    info!("{} startet!", level.name);
+
+    // Boden
+    let ground = &level.ground;
+    let ground_size = ground.size;
+    let mud_material = materials.add(StandardMaterial {
+        base_color_texture: Some(load_repeating_texture(&asset_server, &ground.texture)),
+        normal_map_texture: Some(load_repeating_texture(&asset_server, &ground.normal_map)),
+        // Glossmap als Roughness-Map
+        metallic_roughness_texture: Some(load_repeating_texture(
+            &asset_server,
+            &ground.roughness_map,
+        )),
+        perceptual_roughness: ground.perceptual_roughness,
+        reflectance: ground.reflectance,
+        ..default()
+    });
+
+    let mesh_handle = meshes.add(Mesh::from(Plane3d {
+        normal: Dir3::Y,
+        half_size: Vec2::splat(ground_size / 2.0),
+    }));
+
+    // UVs anpassen für Kachelung
+    if let Some(mesh) = meshes.get_mut(&mesh_handle)
+        && let Some(VertexAttributeValues::Float32x2(uvs)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0)
+    {
+        for uv in uvs.iter_mut() {
+            uv[0] *= ground_size / ground.texture_tile_size;
+            uv[1] *= ground_size / ground.texture_tile_size;
+        }
+    }
+
+    commands.spawn((
+        Ground,
+        LevelEntity,
+        Mesh3d(mesh_handle),
+        MeshMaterial3d(mud_material),
+        Transform::from_xyz(0.0, -0.05, 0.0),
+        Visibility::Visible,
+        RigidBody::Fixed,
+        Collider::cuboid(ground_size / 2.0, 0.05, ground_size / 2.0),
+        Friction {
+            coefficient: ground.friction,
+            combine_rule: CoefficientCombineRule::Average,
+        },
+        ActiveEvents::COLLISION_EVENTS,
+        ActiveCollisionTypes::DYNAMIC_STATIC,
+    ));
+
+    // Wände an den Levelgrenzen
+    let walls = &level.walls;
+    let [r, g, b] = walls.color;
+    let wall_material = materials.add(Color::srgb(r, g, b));
+    let bounds = level.bounds();
+    let y = walls.height / 2.0;
+    let along_x = Vec3::new(ground_size, walls.height, walls.thickness);
+    let along_z = Vec3::new(walls.thickness, walls.height, ground_size);
+    for (size, position) in [
+        (along_x, Vec3::new(0.0, y, bounds)),  // Nord
+        (along_x, Vec3::new(0.0, y, -bounds)), // Süd
+        (along_z, Vec3::new(bounds, y, 0.0)),  // Ost
+        (along_z, Vec3::new(-bounds, y, 0.0)), // West
+    ] {
+        spawn_wall(
+            &mut commands,
+            &mut meshes,
+            wall_material.clone(),
+            size,
+            position,
+        );
+    }
+}
+
+// Walls-Hilfsfunktion (wie gehabt)
+fn spawn_wall(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    material: Handle<StandardMaterial>,
+    size: Vec3,
+    position: Vec3,
+) {
+    commands.spawn((
+        LevelEntity,
+        Wall,
+        Mesh3d(meshes.add(Cuboid::new(size.x, size.y, size.z))),
+        MeshMaterial3d(material.clone()),
+        Transform::from_translation(position),
+        Visibility::Visible,
+        RigidBody::Fixed,
+        Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+    ));
+}
@@ -1,12 +1,25 @@
+pub mod aiming;
+pub mod asset_cache;
 pub mod background_music_plugin;
 pub mod bullet;
 pub mod camera;
 pub mod enemy;
+pub mod enemy_ai;
 pub mod gui;
 pub mod health;
+pub mod level;
 pub mod network;
+pub mod particles;
 pub mod pause_menu_gui;
+pub mod perception;
+pub mod pickup;
 pub mod player;
+pub mod score;
 pub mod skybox_plugin;
 pub mod explosion;
+pub mod game_over_gui;
+pub mod ground_mark;
+pub mod grounded;
 pub mod tree;
+pub mod wave;
+pub mod weapon;
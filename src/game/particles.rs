@@ -0,0 +1,414 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_common_assets::json::JsonAssetPlugin;
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    AppState,
+    assets::{
+        AssetLoadingState,
+        particle_effects::{ParticleEffect, ParticleEmitter, ParticleVisual},
+    },
+};
+
+use super::asset_cache::GameAssetCache;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(JsonAssetPlugin::<ParticleEffect>::new(&["particles.json"]))
+            .add_event::<ParticleEvent>()
+            .init_resource::<ParticlePool>()
+            .add_systems(Startup, setup)
+            .add_systems(Update, preload_particle_scenes)
+            .add_systems(OnEnter(AssetLoadingState::Loading), clear_particles)
+            .add_systems(
+                Update,
+                (spawn_particles_system, particle_update_system)
+                    .chain()
+                    .run_if(in_state(AppState::Running)),
+            );
+    }
+}
+
+// Obergrenze gleichzeitig lebender Partikel, darüber werden Effekte ausgedünnt
+const MAX_PARTICLES: usize = 1000;
+
+// Vordefinierte Effekte, jeweils als JSON-Asset
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParticlePreset {
+    Explosion,
+    Uproot,
+}
+
+impl ParticlePreset {
+    const ALL: [ParticlePreset; 2] = [ParticlePreset::Explosion, ParticlePreset::Uproot];
+
+    fn path(&self) -> &'static str {
+        match self {
+            ParticlePreset::Explosion => "particles/explosion.particles.json",
+            ParticlePreset::Uproot => "particles/uproot.particles.json",
+        }
+    }
+}
+
+// Effekt an einer Stelle auslösen. spread skaliert die Startbox der Emitter
+// (z.B. Stammradius beim Entwurzeln)
+#[derive(Event, Clone, Debug)]
+pub struct ParticleEvent {
+    pub preset: ParticlePreset,
+    pub position: Vec3,
+    pub spread: f32,
+}
+
+impl ParticleEvent {
+    pub fn new(preset: ParticlePreset, position: Vec3) -> Self {
+        ParticleEvent {
+            preset,
+            position,
+            spread: 0.0,
+        }
+    }
+
+    pub fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+}
+
+#[derive(Resource)]
+struct ParticleEffects {
+    handles: HashMap<ParticlePreset, Handle<ParticleEffect>>,
+    // Szenen aus den Effekten, vorgeladen damit sie beim ersten Auslösen da sind
+    scenes: HashMap<String, Handle<Scene>>,
+}
+
+// Wiederverwendbare Entities je Darstellung. Kugeln behalten ihr Material,
+// so entstehen nach dem Aufwärmen keine neuen Meshes oder Materialien mehr.
+#[derive(Resource, Default)]
+struct ParticlePool {
+    free: HashMap<PoolKey, Vec<PooledParticle>>,
+    alive: usize,
+}
+
+impl ParticlePool {
+    // Zurück in den Pool
+    fn release(&mut self, entity: Entity, particle: &mut Particle, visibility: &mut Visibility) {
+        particle.alive = false;
+        *visibility = Visibility::Hidden;
+        let material = particle.color.take().map(|color| color.material);
+        self.free
+            .entry(particle.key.clone())
+            .or_default()
+            .push((entity, material));
+        self.alive = self.alive.saturating_sub(1);
+    }
+}
+
+// Freie Entity samt Material (nur bei Kugeln)
+type PooledParticle = (Entity, Option<Handle<StandardMaterial>>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum PoolKey {
+    Sphere,
+    Scene(String),
+}
+
+#[derive(Component)]
+struct Particle {
+    alive: bool,
+    key: PoolKey,
+    lifetime: Timer,
+    velocity: Vec3,
+    angular_velocity: Vec3,
+    gravity: f32,
+    size: f32,
+    size_over_life: (f32, f32),
+    color: Option<ParticleColor>,
+}
+
+struct ParticleColor {
+    material: Handle<StandardMaterial>,
+    start: Color,
+    end: Color,
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handles = ParticlePreset::ALL
+        .iter()
+        .map(|preset| (*preset, asset_server.load(preset.path())))
+        .collect();
+    commands.insert_resource(ParticleEffects {
+        handles,
+        scenes: HashMap::default(),
+    });
+}
+
+fn preload_particle_scenes(
+    mut asset_events: EventReader<AssetEvent<ParticleEffect>>,
+    effect_assets: Res<Assets<ParticleEffect>>,
+    mut effects: ResMut<ParticleEffects>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in asset_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        let Some(effect) = effect_assets.get(*id) else {
+            continue;
+        };
+        for emitter in &effect.emitters {
+            if let ParticleVisual::Scenes { paths } = &emitter.visual {
+                for path in paths {
+                    if !effects.scenes.contains_key(path) {
+                        effects.scenes.insert(path.clone(), asset_server.load(path));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn random_range(rng: &mut impl Rng, (min, max): (f32, f32)) -> f32 {
+    if max > min {
+        rng.random_range(min..=max)
+    } else {
+        min
+    }
+}
+
+// Richtung aus Winkel zur Hochachse (Grad) und zufälligem Azimut
+fn random_direction(rng: &mut impl Rng, polar_angle: (f32, f32)) -> Vec3 {
+    let polar = random_range(rng, polar_angle).to_radians();
+    let azimuth = rng.random_range(0.0..std::f32::consts::TAU);
+    Vec3::new(
+        polar.sin() * azimuth.cos(),
+        polar.cos(),
+        polar.sin() * azimuth.sin(),
+    )
+}
+
+fn vary_color([r, g, b, a]: [f32; 4], variance: f32, rng: &mut impl Rng) -> Color {
+    let mut vary = |channel: f32| {
+        if variance > 0.0 {
+            (channel + rng.random_range(-variance..variance)).clamp(0.0, 1.0)
+        } else {
+            channel
+        }
+    };
+    Color::srgba(vary(r), vary(g), vary(b), a)
+}
+
+fn spawn_particles_system(
+    mut commands: Commands,
+    mut particle_events: EventReader<ParticleEvent>,
+    effects: Res<ParticleEffects>,
+    effect_assets: Res<Assets<ParticleEffect>>,
+    cache: Res<GameAssetCache>,
+    mut pool: ResMut<ParticlePool>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut rng = rand::rng();
+    for event in particle_events.read() {
+        let Some(effect) = effects
+            .handles
+            .get(&event.preset)
+            .and_then(|handle| effect_assets.get(handle))
+        else {
+            continue;
+        };
+        for emitter in &effect.emitters {
+            let budget = MAX_PARTICLES.saturating_sub(pool.alive);
+            let count = (rng.random_range(emitter.count.0..=emitter.count.1.max(emitter.count.0))
+                as usize)
+                .min(budget);
+            for _ in 0..count {
+                spawn_particle(
+                    &mut commands,
+                    &mut pool,
+                    &effects,
+                    &cache,
+                    &mut materials,
+                    emitter,
+                    event,
+                    &mut rng,
+                );
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_particle(
+    commands: &mut Commands,
+    pool: &mut ParticlePool,
+    effects: &ParticleEffects,
+    cache: &GameAssetCache,
+    materials: &mut Assets<StandardMaterial>,
+    emitter: &ParticleEmitter,
+    event: &ParticleEvent,
+    rng: &mut impl Rng,
+) {
+    let [ox, oy, oz] = emitter.offset;
+    let [sx, sy, sz] = emitter.spread.map(|extent| extent * event.spread);
+    let position = event.position
+        + Vec3::new(ox, oy, oz)
+        + Vec3::new(
+            random_range(rng, (-sx, sx)),
+            random_range(rng, (-sy, sy)),
+            random_range(rng, (-sz, sz)),
+        );
+    let rotation = if emitter.random_rotation {
+        Quat::from_euler(
+            EulerRot::XYZ,
+            rng.random_range(0.0..std::f32::consts::TAU),
+            rng.random_range(0.0..std::f32::consts::TAU),
+            rng.random_range(0.0..std::f32::consts::TAU),
+        )
+    } else {
+        Quat::IDENTITY
+    };
+    let size = random_range(rng, emitter.size);
+    let transform = Transform::from_translation(position)
+        .with_rotation(rotation)
+        .with_scale(Vec3::splat(size * emitter.size_over_life.0));
+
+    let (key, color) = match &emitter.visual {
+        ParticleVisual::Sphere {
+            start_color,
+            end_color,
+            color_variance,
+            ..
+        } => (
+            PoolKey::Sphere,
+            Some((
+                vary_color(*start_color, *color_variance, rng),
+                vary_color(*end_color, *color_variance, rng),
+            )),
+        ),
+        ParticleVisual::Scenes { paths } => {
+            // Noch nicht vorgeladene Szenen auslassen
+            let Some(path) = paths
+                .choose(rng)
+                .filter(|path| effects.scenes.contains_key(*path))
+            else {
+                return;
+            };
+            (PoolKey::Scene(path.clone()), None)
+        }
+    };
+
+    let mut particle = Particle {
+        alive: true,
+        key: key.clone(),
+        lifetime: Timer::from_seconds(random_range(rng, emitter.lifetime), TimerMode::Once),
+        velocity: random_direction(rng, emitter.polar_angle) * random_range(rng, emitter.speed),
+        angular_velocity: random_direction(rng, (0.0, 180.0))
+            * rng.random_range(-1.0..=1.0)
+            * emitter.rotation_speed,
+        gravity: emitter.gravity,
+        size,
+        size_over_life: emitter.size_over_life,
+        color: None,
+    };
+
+    let reused = pool.free.get_mut(&key).and_then(|free| free.pop());
+    let (entity, material) = match reused {
+        Some(pooled) => pooled,
+        None => match &key {
+            PoolKey::Sphere => {
+                let material = materials.add(StandardMaterial::default());
+                let entity = commands
+                    .spawn((
+                        Mesh3d(cache.unit_sphere.clone()),
+                        MeshMaterial3d(material.clone()),
+                    ))
+                    .id();
+                (entity, Some(material))
+            }
+            PoolKey::Scene(path) => {
+                let scene = effects.scenes[path].clone();
+                (commands.spawn(SceneRoot(scene)).id(), None)
+            }
+        },
+    };
+
+    // Material der (evtl. wiederverwendeten) Kugel auf den Startzustand setzen
+    if let (Some(material), Some((start, end))) = (material, color) {
+        if let Some(mat) = materials.get_mut(&material) {
+            mat.base_color = start;
+            mat.alpha_mode = if start.alpha() < 1.0 || end.alpha() < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            };
+            if let ParticleVisual::Sphere {
+                perceptual_roughness,
+                ..
+            } = emitter.visual
+            {
+                mat.perceptual_roughness = perceptual_roughness
+                    .unwrap_or(StandardMaterial::default().perceptual_roughness);
+            }
+        }
+        particle.color = Some(ParticleColor {
+            material,
+            start,
+            end,
+        });
+    }
+
+    commands
+        .entity(entity)
+        .insert((transform, Visibility::Visible, particle));
+    pool.alive += 1;
+}
+
+fn particle_update_system(
+    time: Res<Time>,
+    mut pool: ResMut<ParticlePool>,
+    mut query: Query<(Entity, &mut Particle, &mut Transform, &mut Visibility)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut particle, mut transform, mut visibility) in query.iter_mut() {
+        if !particle.alive {
+            continue;
+        }
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            pool.release(entity, &mut particle, &mut visibility);
+            continue;
+        }
+
+        let t = particle.lifetime.fraction();
+        particle.velocity.y -= particle.gravity * dt;
+        transform.translation += particle.velocity * dt;
+        if particle.angular_velocity != Vec3::ZERO {
+            transform.rotation =
+                Quat::from_scaled_axis(particle.angular_velocity * dt) * transform.rotation;
+        }
+        let (start_size, end_size) = particle.size_over_life;
+        transform.scale = Vec3::splat(particle.size * start_size.lerp(end_size, t));
+
+        if let Some(color) = &particle.color
+            && color.start != color.end
+            && let Some(material) = materials.get_mut(&color.material)
+        {
+            material.base_color = color.start.mix(&color.end, t);
+        }
+    }
+}
+
+// Beim Levelwechsel alle noch fliegenden Partikel einsammeln
+fn clear_particles(
+    mut pool: ResMut<ParticlePool>,
+    mut query: Query<(Entity, &mut Particle, &mut Visibility)>,
+) {
+    for (entity, mut particle, mut visibility) in query.iter_mut() {
+        if particle.alive {
+            pool.release(entity, &mut particle, &mut visibility);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{AppState, Player};
+
+use super::{enemy::Enemy, level::Wall, tree::TreeCollider};
+
+pub struct PerceptionPlugin;
+
+impl Plugin for PerceptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            enemy_perception_system.run_if(in_state(AppState::Running)),
+        );
+    }
+}
+
+// Was ein Gegner vom Spieler weiß
+#[derive(Component, Debug)]
+pub struct Perception {
+    pub view_distance: f32,
+    pub view_angle: f32, // halber Öffnungswinkel in Bogenmaß
+    pub memory: Timer,   // wie lange die letzte Position gemerkt wird
+    pub sees_player: bool,
+    pub last_known_position: Option<Vec3>,
+}
+
+impl Perception {
+    pub fn new(view_distance: f32, view_angle_degrees: f32, memory_secs: f32) -> Self {
+        Perception {
+            view_distance,
+            view_angle: view_angle_degrees.to_radians(),
+            memory: Timer::from_seconds(memory_secs, TimerMode::Once),
+            sees_player: false,
+            last_known_position: None,
+        }
+    }
+
+    // Sichtbar: Spieler selbst, sonst Erinnerung
+    pub fn target_position(&self, player_pos: Vec3) -> Option<Vec3> {
+        if self.sees_player {
+            Some(player_pos)
+        } else {
+            self.last_known_position
+        }
+    }
+}
+
+// Liegt das Ziel im Sichtkegel und in Sichtweite?
+pub fn in_view_cone(forward: Vec3, to_target: Vec3, view_distance: f32, view_angle: f32) -> bool {
+    let distance = to_target.length();
+    if distance > view_distance {
+        return false;
+    }
+    if distance < f32::EPSILON {
+        return true;
+    }
+    forward.angle_between(to_target) <= view_angle
+}
+
+// Abstand, ab dem die letzte bekannte Position als erreicht gilt
+const LAST_KNOWN_REACHED: f32 = 2.0;
+
+#[allow(clippy::type_complexity)]
+pub fn enemy_perception_system(
+    time: Res<Time>,
+    rapier_context: ReadRapierContext,
+    player_query: Single<(Entity, &Transform), With<Player>>,
+    mut enemy_query: Query<(Entity, &Enemy, &Transform, &mut Perception)>,
+    obstacle_query: Query<(), Or<(With<TreeCollider>, With<Wall>)>>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+    let (player_entity, player_transform) = player_query.into_inner();
+    let player_pos = player_transform.translation;
+    let is_obstacle = |e: Entity| obstacle_query.contains(e);
+
+    for (entity, enemy, transform, mut perception) in enemy_query.iter_mut() {
+        // Augenhöhe etwas über der Mitte
+        let eye = transform.translation + Vec3::Y * enemy.size.y * 0.3;
+        let to_player = player_pos - eye;
+        let distance = to_player.length();
+
+        let mut sees_player = in_view_cone(
+            *transform.forward(),
+            to_player,
+            perception.view_distance,
+            perception.view_angle,
+        );
+
+        // Sichtlinie: Bäume und Wände verdecken den Spieler
+        if sees_player && distance > f32::EPSILON {
+            let filter = QueryFilter::default()
+                .exclude_rigid_body(entity)
+                .exclude_rigid_body(player_entity)
+                .exclude_sensors()
+                .predicate(&is_obstacle);
+            sees_player = rapier_context
+                .cast_ray(eye, to_player / distance, distance, true, filter)
+                .is_none();
+        }
+
+        perception.sees_player = sees_player;
+        if sees_player {
+            perception.last_known_position = Some(player_pos);
+            perception.memory.reset();
+            continue;
+        }
+
+        perception.memory.tick(time.delta());
+        let reached = perception
+            .last_known_position
+            .is_some_and(|pos| pos.xz().distance(transform.translation.xz()) < LAST_KNOWN_REACHED);
+        if perception.memory.finished() || reached {
+            perception.last_known_position = None;
+        }
+    }
+}
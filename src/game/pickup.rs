@@ -0,0 +1,387 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    AppState, Health, Player,
+    assets::{AssetLoadingState, levels::Level},
+};
+
+use super::{
+    asset_cache::GameAssetCache,
+    health::{DeathEvent, HealEvent},
+    level::{CurrentLevel, LevelEntity},
+    player::PLAYER_MAX_HEALTH,
+    tree::TreeRoot,
+    weapon::PlayerWeapon,
+};
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(OnExit(AppState::GameOver), clear_pickups)
+            .add_systems(OnEnter(AssetLoadingState::Done), reset_pickup_spawner)
+            .add_systems(
+                Update,
+                (
+                    field_pickup_spawn_system,
+                    enemy_drop_system,
+                    pickup_animation_system,
+                    pickup_collect_system,
+                    power_up_timer_system,
+                )
+                    .run_if(in_state(AppState::Running)),
+            );
+    }
+}
+
+const MAX_FIELD_PICKUPS: usize = 6;
+const FIELD_SPAWN_SECS: f32 = 15.0;
+const FIELD_LIFETIME_SECS: f32 = 60.0;
+const DROP_LIFETIME_SECS: f32 = 20.0;
+const DROP_CHANCE: f64 = 0.3;
+// Kein Baum näher als das, aber Wald in Reichweite
+const CLEARING_RADIUS: f32 = 4.0;
+const FOREST_RADIUS: f32 = 15.0;
+const PICKUP_HEIGHT: f32 = 0.8;
+const BLINK_SECS: f32 = 3.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PickupKind {
+    Health(f32),
+    Ammo(u32),
+    RapidFire(f32),  // Sekunden doppelte Feuerrate
+    SpeedBoost(f32), // Sekunden schnelleres Laufen
+}
+
+impl PickupKind {
+    // Gewichtete Zufallsauswahl
+    fn random(rng: &mut impl Rng) -> Self {
+        let kinds = [
+            (PickupKind::Health(25.0), 4.0),
+            (PickupKind::Ammo(40), 4.0),
+            (PickupKind::RapidFire(8.0), 1.0),
+            (PickupKind::SpeedBoost(8.0), 1.0),
+        ];
+        kinds
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .map_or(PickupKind::Health(25.0), |(kind, _)| *kind)
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            PickupKind::Health(_) => Color::srgb(0.2, 1.0, 0.3),
+            PickupKind::Ammo(_) => Color::srgb(1.0, 0.8, 0.2),
+            PickupKind::RapidFire(_) => Color::srgb(1.0, 0.3, 0.1),
+            PickupKind::SpeedBoost(_) => Color::srgb(0.2, 0.8, 1.0),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    pub lifetime: Timer,
+    base_y: f32,
+}
+
+// Im Wald verteilt (im Gegensatz zu Drops von Gegnern)
+#[derive(Component)]
+struct FieldPickup;
+
+// Zeitlich begrenzte Verstärkungen des Spielers (Timer starten abgelaufen)
+#[derive(Component)]
+pub struct PowerUps {
+    pub rapid_fire: Timer,
+    pub speed_boost: Timer,
+}
+
+impl PowerUps {
+    pub fn fire_rate_multiplier(&self) -> f32 {
+        if self.rapid_fire.finished() { 1.0 } else { 2.0 }
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.speed_boost.finished() {
+            1.0
+        } else {
+            1.5
+        }
+    }
+}
+
+impl Default for PowerUps {
+    fn default() -> Self {
+        PowerUps {
+            rapid_fire: finished_timer(),
+            speed_boost: finished_timer(),
+        }
+    }
+}
+
+fn finished_timer() -> Timer {
+    let mut timer = Timer::from_seconds(0.0, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
+#[derive(Resource, Clone)]
+struct PickupSound(Handle<AudioSource>);
+
+#[derive(Resource)]
+struct PickupSpawner {
+    timer: Timer,
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PickupSound(asset_server.load("sounds/pickup.wav")));
+    commands.insert_resource(PickupSpawner {
+        timer: Timer::from_seconds(FIELD_SPAWN_SECS, TimerMode::Repeating),
+    });
+}
+
+fn reset_pickup_spawner(mut spawner: ResMut<PickupSpawner>) {
+    spawner.timer.reset();
+}
+
+pub fn spawn_pickup(
+    commands: &mut Commands,
+    cache: &mut GameAssetCache,
+    materials: &mut Assets<StandardMaterial>,
+    kind: PickupKind,
+    position: Vec3,
+    lifetime_secs: f32,
+) -> Entity {
+    let color = kind.color();
+    let material = cache.material(
+        materials,
+        &format!("pickup:{:?}", color.to_srgba().to_u8_array()),
+        || StandardMaterial {
+            base_color: color,
+            emissive: color.to_linear() * 2.0,
+            ..default()
+        },
+    );
+    commands
+        .spawn((
+            Pickup {
+                kind,
+                lifetime: Timer::from_seconds(lifetime_secs, TimerMode::Once),
+                base_y: PICKUP_HEIGHT,
+            },
+            LevelEntity,
+            Mesh3d(cache.pickup_mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(position.with_y(PICKUP_HEIGHT)),
+            Visibility::Visible,
+            Collider::ball(0.6),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+        ))
+        .id()
+}
+
+// Lichtung: kein Baum zu nah, aber mindestens einer in der Nähe
+pub fn is_clearing(position: Vec2, trees: &[Vec2]) -> bool {
+    let mut near_forest = false;
+    for tree in trees {
+        let distance = tree.distance(position);
+        if distance < CLEARING_RADIUS {
+            return false;
+        }
+        near_forest |= distance < FOREST_RADIUS;
+    }
+    near_forest
+}
+
+#[allow(clippy::too_many_arguments)]
+fn field_pickup_spawn_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spawner: ResMut<PickupSpawner>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+    player: Single<&Transform, With<Player>>,
+    field_pickups: Query<(), With<FieldPickup>>,
+    trees: Query<&Transform, With<TreeRoot>>,
+    mut cache: ResMut<GameAssetCache>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    spawner.timer.tick(time.delta());
+    if !spawner.timer.just_finished() {
+        return;
+    }
+    // Drops von Gegnern zählen nicht mit
+    if field_pickups.iter().count() >= MAX_FIELD_PICKUPS {
+        return;
+    }
+    let Some(level) = current_level.get(&levels) else {
+        return;
+    };
+
+    let tree_positions: Vec<Vec2> = trees.iter().map(|t| t.translation.xz()).collect();
+    let bounds = level.bounds() - 2.0;
+    let center = player.translation.xz();
+    let mut rng = rand::rng();
+
+    // Einige Versuche rund um den Spieler, damit man die Pickups auch findet
+    for _ in 0..30 {
+        let angle = rng.random_range(0.0..std::f32::consts::TAU);
+        let distance = rng.random_range(15.0..60.0);
+        let candidate = (center + Vec2::from_angle(angle) * distance)
+            .clamp(Vec2::splat(-bounds), Vec2::splat(bounds));
+        if is_clearing(candidate, &tree_positions) {
+            let pickup = spawn_pickup(
+                &mut commands,
+                &mut cache,
+                &mut materials,
+                PickupKind::random(&mut rng),
+                Vec3::new(candidate.x, 0.0, candidate.y),
+                FIELD_LIFETIME_SECS,
+            );
+            commands.entity(pickup).insert(FieldPickup);
+            return;
+        }
+    }
+}
+
+// Getötete Gegner lassen manchmal etwas fallen
+fn enemy_drop_system(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    player: Single<Entity, With<Player>>,
+    transform_query: Query<&Transform>,
+    mut cache: ResMut<GameAssetCache>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut rng = rand::rng();
+    for event in death_events.read() {
+        if event.target == *player || !rng.random_bool(DROP_CHANCE) {
+            continue;
+        }
+        // event.position ist der Trefferpunkt, bei Explosionen also deren Mitte
+        let position = transform_query
+            .get(event.target)
+            .map_or(event.position, |transform| transform.translation);
+        spawn_pickup(
+            &mut commands,
+            &mut cache,
+            &mut materials,
+            PickupKind::random(&mut rng),
+            position,
+            DROP_LIFETIME_SECS,
+        );
+    }
+}
+
+// Auf und ab wippen, drehen und kurz vor dem Verschwinden blinken
+fn pickup_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Pickup, &mut Transform, &mut Visibility)>,
+) {
+    let t = time.elapsed_secs();
+    for (entity, mut pickup, mut transform, mut visibility) in query.iter_mut() {
+        pickup.lifetime.tick(time.delta());
+        if pickup.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let phase = transform.translation.x + transform.translation.z;
+        transform.translation.y = pickup.base_y + (t * 2.5 + phase).sin() * 0.2;
+        transform.rotate_y(time.delta_secs() * 1.5);
+
+        let remaining = pickup.lifetime.remaining_secs();
+        *visibility = if remaining < BLINK_SECS && (remaining * 25.0).sin() < 0.0 {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        };
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn pickup_collect_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut heal_events: EventWriter<HealEvent>,
+    player: Single<(Entity, &Health, &mut PlayerWeapon, &mut PowerUps), With<Player>>,
+    pickups: Query<(&Pickup, &Transform)>,
+    pickup_sound: Res<PickupSound>,
+) {
+    let (player_entity, health, mut weapon, mut power_ups) = player.into_inner();
+
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = event else {
+            continue;
+        };
+        let pickup_entity = if *e1 == player_entity {
+            *e2
+        } else if *e2 == player_entity {
+            *e1
+        } else {
+            continue;
+        };
+        let Ok((pickup, transform)) = pickups.get(pickup_entity) else {
+            continue;
+        };
+
+        match pickup.kind {
+            PickupKind::Health(amount) => {
+                // Bei voller Gesundheit liegen lassen
+                let missing = PLAYER_MAX_HEALTH - health.value;
+                if missing <= 0.0 {
+                    continue;
+                }
+                heal_events.write(HealEvent {
+                    source: Some(pickup_entity),
+                    target: player_entity,
+                    amount: amount.min(missing),
+                    position: transform.translation,
+                });
+            }
+            PickupKind::Ammo(amount) => {
+                if weapon.ammo >= weapon.max_ammo {
+                    continue;
+                }
+                weapon.ammo = (weapon.ammo + amount).min(weapon.max_ammo);
+            }
+            PickupKind::RapidFire(secs) => {
+                power_ups.rapid_fire = Timer::from_seconds(secs, TimerMode::Once);
+            }
+            PickupKind::SpeedBoost(secs) => {
+                power_ups.speed_boost = Timer::from_seconds(secs, TimerMode::Once);
+            }
+        }
+
+        commands.entity(pickup_entity).despawn();
+        commands.spawn((
+            AudioPlayer::new(pickup_sound.0.clone()),
+            PlaybackSettings::DESPAWN.with_spatial(true),
+            Transform::from_translation(transform.translation),
+        ));
+    }
+}
+
+fn power_up_timer_system(time: Res<Time>, mut query: Query<&mut PowerUps>) {
+    for mut power_ups in query.iter_mut() {
+        power_ups.rapid_fire.tick(time.delta());
+        power_ups.speed_boost.tick(time.delta());
+    }
+}
+
+fn clear_pickups(
+    mut commands: Commands,
+    pickups: Query<Entity, With<Pickup>>,
+    mut power_ups: Query<&mut PowerUps>,
+) {
+    for entity in pickups.iter() {
+        commands.entity(entity).despawn();
+    }
+    for mut power_ups in power_ups.iter_mut() {
+        *power_ups = PowerUps::default();
+    }
+}
@@ -3,20 +3,42 @@ use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use crate::AppState;
+use crate::{
+    AppState,
+    assets::{AssetLoadingState, levels::Level},
+};
 
-use super::camera::CameraControl;
-use super::health::Health;
+use super::camera::{CameraControl, CameraShake};
+use super::grounded::{Grounded, ground_detection_system};
+use super::health::{DamageEvent, Health};
+use super::level::CurrentLevel;
+use super::pickup::PowerUps;
+use super::weapon::PlayerWeapon;
+
+const PLAYER_SPAWN_HEIGHT: f32 = 0.6;
+pub const PLAYER_MAX_HEALTH: f32 = 100.0;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup).add_systems(
-            Update,
-            (player_align_to_camera_system, player_movement_system)
-                .run_if(in_state(crate::AppState::Running)),
-        );
+        app.add_systems(Startup, setup)
+            .add_systems(OnExit(AppState::GameOver), respawn_player)
+            .add_systems(
+                OnEnter(AssetLoadingState::Done),
+                place_player_at_level_start,
+            )
+            .add_systems(
+                Update,
+                (
+                    player_align_to_camera_system,
+                    player_hit_camera_shake_system,
+                    (player_jump_system, player_movement_system)
+                        .chain()
+                        .after(ground_detection_system),
+                )
+                    .run_if(in_state(crate::AppState::Running)),
+            );
     }
 }
 
@@ -24,14 +46,61 @@ impl Plugin for PlayerPlugin {
 pub struct Player {
     pub speed: f32,
     pub acceleration: f32,
+    pub air_acceleration: f32, // Beschleunigung in der Luft (Air Control)
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Player::new(12.0, 60.0, 15.0)
+    }
 }
 
 impl Player {
-    pub fn new(speed: f32, acceleration: f32) -> Self {
-        Player { speed, acceleration }
+    pub fn new(speed: f32, acceleration: f32, air_acceleration: f32) -> Self {
+        Player {
+            speed,
+            acceleration,
+            air_acceleration,
+        }
     }
 }
 
+#[derive(Component)]
+pub struct PlayerJump {
+    pub impulse: f32,
+    // Läuft ab, nachdem der Spieler den Boden verlassen hat (Coyote Time)
+    pub coyote_timer: Timer,
+    // Merkt sich einen zu früh gedrückten Sprung (Jump Buffering)
+    pub buffer_timer: Timer,
+}
+
+impl PlayerJump {
+    pub fn new(impulse: f32, coyote_secs: f32, buffer_secs: f32) -> Self {
+        PlayerJump {
+            impulse,
+            coyote_timer: finished_timer(coyote_secs),
+            buffer_timer: finished_timer(buffer_secs),
+        }
+    }
+
+    fn can_jump(&self, grounded: &Grounded) -> bool {
+        grounded.on_ground || !self.coyote_timer.finished()
+    }
+}
+
+impl Default for PlayerJump {
+    fn default() -> Self {
+        PlayerJump::new(20.0, 0.15, 0.15)
+    }
+}
+
+// Timer, der bereits abgelaufen ist
+fn finished_timer(secs: f32) -> Timer {
+    let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -39,11 +108,13 @@ fn setup(
 ) {
     // Player (blauer Würfel)
     commands.spawn((
-        Player { speed: 12.0, acceleration: 60.0 }, // <--- Beschleunigung hier setzen!
-        Health { value: 100.0 },
+        Player::default(), // <--- Beschleunigung in Player::default setzen!
+        Health {
+            value: PLAYER_MAX_HEALTH,
+        },
         Mesh3d(meshes.add(Cuboid::new(1.2, 1.2, 1.2))),
         MeshMaterial3d(materials.add(Color::from(Srgba::new(0.2, 0.2, 1.0, 1.0)))),
-        Transform::from_xyz(0.0, 0.6, 0.0),
+        Transform::from_xyz(0.0, PLAYER_SPAWN_HEIGHT, 0.0),
         Visibility::Visible,
         RigidBody::Dynamic,
         Collider::cuboid(0.6, 0.6, 0.6),
@@ -62,16 +133,114 @@ fn setup(
                 linear_damping: 0.5,
                 angular_damping: 2.0,
             },
+            PlayerWeapon::default(),
+            PlayerJump::default(),
+            Grounded::new(Vec3::splat(0.6)),
+            PowerUps::default(),
         ),
     ));
 }
 
+fn level_start_position(current_level: &CurrentLevel, levels: &Assets<Level>) -> Vec3 {
+    let (x, z) = current_level
+        .get(levels)
+        .map(|level| level.player_start_position)
+        .unwrap_or_default();
+    Vec3::new(x, PLAYER_SPAWN_HEIGHT, z)
+}
+
+// Nach jedem (Neu-)Laden eines Levels an den Startpunkt setzen
+fn place_player_at_level_start(
+    player: Single<(&mut Transform, &mut Velocity), With<Player>>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+) {
+    let (mut transform, mut velocity) = player.into_inner();
+    *transform = Transform::from_translation(level_start_position(&current_level, &levels));
+    *velocity = Velocity::zero();
+}
+
+// Spieler nach GameOver auf Startzustand zurücksetzen
+#[allow(clippy::type_complexity)]
+fn respawn_player(
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+    player: Single<
+        (
+            &mut Player,
+            &mut Health,
+            &mut Transform,
+            &mut Velocity,
+            &mut ExternalForce,
+            &mut ExternalImpulse,
+            &mut PlayerJump,
+            &mut PlayerWeapon,
+        ),
+        With<Player>,
+    >,
+) {
+    let (
+        mut player,
+        mut health,
+        mut transform,
+        mut velocity,
+        mut force,
+        mut impulse,
+        mut jump,
+        mut weapon,
+    ) = player.into_inner();
+    *player = Player::default();
+    health.value = PLAYER_MAX_HEALTH;
+    *transform = Transform::from_translation(level_start_position(&current_level, &levels));
+    *velocity = Velocity::zero();
+    *force = ExternalForce::default();
+    *impulse = ExternalImpulse::default();
+    *jump = PlayerJump::default();
+    *weapon = PlayerWeapon::default();
+}
+
+fn player_jump_system(
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut query: Query<(&mut PlayerJump, &Grounded, &mut ExternalImpulse), With<Player>>,
+) {
+    for (mut jump, grounded, mut impulse) in query.iter_mut() {
+        if grounded.on_ground {
+            jump.coyote_timer.reset();
+        } else {
+            jump.coyote_timer.tick(time.delta());
+        }
+
+        if input.just_pressed(KeyCode::Space) {
+            jump.buffer_timer.reset();
+        } else {
+            jump.buffer_timer.tick(time.delta());
+        }
+
+        if !jump.buffer_timer.finished() && jump.can_jump(grounded) {
+            impulse.impulse += Vec3::Y * jump.impulse;
+            // Sprung verbrauchen, damit er nicht doppelt ausgelöst wird
+            let buffer_duration = jump.buffer_timer.duration();
+            jump.buffer_timer.tick(buffer_duration);
+            let coyote_duration = jump.coyote_timer.duration();
+            jump.coyote_timer.tick(coyote_duration);
+        }
+    }
+}
+
 fn player_movement_system(
     input: Res<ButtonInput<KeyCode>>,
     camera_control: Res<crate::game::camera::CameraControl>,
-    mut query: Query<(&Player, &mut Transform, &Velocity, &mut ExternalForce)>,
+    mut query: Query<(
+        &Player,
+        &Grounded,
+        &PowerUps,
+        &mut Transform,
+        &Velocity,
+        &mut ExternalForce,
+    )>,
 ) {
-    for (player, mut transform, velocity, mut force) in query.iter_mut() {
+    for (player, grounded, power_ups, mut transform, velocity, mut force) in query.iter_mut() {
         let mut move_dir = Vec3::ZERO;
 
         // Kamera-Forward und Right auf XZ-Ebene berechnen
@@ -93,8 +262,13 @@ fn player_movement_system(
         }
         move_dir = move_dir.normalize_or_zero();
 
-        let move_force = player.acceleration; // <-- jetzt aus Player struct!
-        let max_speed = player.speed;
+        // In der Luft nur eingeschränkte Kontrolle
+        let move_force = if grounded.on_ground {
+            player.acceleration // <-- jetzt aus Player struct!
+        } else {
+            player.air_acceleration
+        };
+        let max_speed = player.speed * power_ups.speed_multiplier();
 
         let vel_in_dir = velocity.linvel.dot(move_dir);
         let force_in_dir = force.force.dot(move_dir);
@@ -142,3 +316,16 @@ fn player_align_to_camera_system(
 
     force.torque = Vec3::Y * torque;
 }
+
+// Treffer am Spieler rütteln die Kamera je nach Schaden
+fn player_hit_camera_shake_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut shake_events: EventWriter<CameraShake>,
+    player: Single<Entity, With<Player>>,
+) {
+    for event in damage_events.read() {
+        if event.target == *player {
+            shake_events.write(CameraShake::global((event.amount / 40.0).clamp(0.1, 0.6)));
+        }
+    }
+}
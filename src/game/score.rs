@@ -0,0 +1,279 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{AppState, Enemy, Player};
+
+use super::health::{DamageKind, DeathEvent, Faction};
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScoreEvent>()
+            .add_event::<KillEvent>()
+            .init_resource::<Score>()
+            .init_resource::<ChainTracker>()
+            .add_systems(Startup, setup_score_hud)
+            .add_systems(OnExit(AppState::GameOver), reset_score)
+            .add_systems(
+                Update,
+                (
+                    kill_attribution_system,
+                    kill_score_system,
+                    apply_score_events,
+                    combo_decay_system,
+                    chain_tracker_cleanup_system,
+                    update_score_text,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Running)),
+            );
+    }
+}
+
+const KILL_POINTS: u32 = 100;
+const TREE_POINTS: u32 = 50;
+const CHAIN_LINK_POINTS: u32 = 25;
+// Jeder Treffer im Combo-Fenster erhöht den Multiplikator um diesen Wert
+const COMBO_STEP: f32 = 0.25;
+const MAX_MULTIPLIER: f32 = 5.0;
+const COMBO_DECAY_SECS: f32 = 2.5;
+// So lange bleibt eine explodierte Entity für die Zuordnung von Kills bekannt
+const CHAIN_MEMORY_SECS: f32 = 10.0;
+
+// Zugehörigkeit einer PendingExplosion zu einer Kettenreaktion.
+// depth 0 = erste Explosion, jede weitere ausgelöste Explosion +1
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExplosionChain {
+    pub id: u32,
+    pub depth: u32,
+    pub instigator: Faction, // wer die erste Explosion verursacht hat
+}
+
+impl ExplosionChain {
+    pub fn next_link(&self) -> Self {
+        ExplosionChain {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+}
+
+// Merkt sich, zu welcher Kette eine (evtl. schon explodierte) Entity gehörte
+#[derive(Resource, Default)]
+pub struct ChainTracker {
+    next_id: u32,
+    sources: HashMap<Entity, (ExplosionChain, f32)>,
+}
+
+impl ChainTracker {
+    pub fn start_chain(&mut self, instigator: Faction) -> ExplosionChain {
+        self.next_id += 1;
+        ExplosionChain {
+            id: self.next_id,
+            depth: 0,
+            instigator,
+        }
+    }
+
+    pub fn register(&mut self, entity: Entity, chain: ExplosionChain, now: f32) {
+        self.sources.insert(entity, (chain, now));
+    }
+
+    pub fn chain_of(&self, entity: Entity) -> Option<ExplosionChain> {
+        self.sources.get(&entity).map(|(chain, _)| *chain)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScoreKind {
+    Kill,
+    TreeUprooted,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct ScoreEvent {
+    pub kind: ScoreKind,
+    pub chain: Option<ExplosionChain>,
+    pub position: Vec3,
+}
+
+// Wer einen Tod verursacht hat, für Punkte und Killfeed
+#[derive(Event, Clone, Debug)]
+pub struct KillEvent {
+    pub victim: Entity,
+    pub killer: Option<Entity>, // Schütze, Rammer bzw. explodierte Entity
+    pub faction: Option<Faction>, // None = nicht zuzuordnen
+    pub kind: DamageKind,
+    pub chain: Option<ExplosionChain>,
+    pub position: Vec3,
+}
+
+// Punkte vor Multiplikator: Grundwert plus Bonus je Kettenglied
+pub fn base_points(kind: ScoreKind, chain: Option<ExplosionChain>) -> u32 {
+    let base = match kind {
+        ScoreKind::Kill => KILL_POINTS,
+        ScoreKind::TreeUprooted => TREE_POINTS,
+    };
+    base + chain.map_or(0, |chain| chain.depth * CHAIN_LINK_POINTS)
+}
+
+#[derive(Resource, Debug)]
+pub struct Score {
+    pub total: u32,
+    pub combo: u32,
+    pub combo_timer: Timer,
+    pub kills: u32,
+    pub trees: u32,
+    pub longest_chain: u32,
+}
+
+impl Score {
+    pub fn multiplier(&self) -> f32 {
+        (1.0 + self.combo as f32 * COMBO_STEP).min(MAX_MULTIPLIER)
+    }
+
+    pub fn add(&mut self, kind: ScoreKind, chain: Option<ExplosionChain>) -> u32 {
+        let points = (base_points(kind, chain) as f32 * self.multiplier()).round() as u32;
+        self.total += points;
+        self.combo += 1;
+        self.combo_timer.reset();
+        match kind {
+            ScoreKind::Kill => self.kills += 1,
+            ScoreKind::TreeUprooted => self.trees += 1,
+        }
+        if let Some(chain) = chain {
+            self.longest_chain = self.longest_chain.max(chain.depth + 1);
+        }
+        points
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Score {
+            total: 0,
+            combo: 0,
+            combo_timer: Timer::from_seconds(COMBO_DECAY_SECS, TimerMode::Once),
+            kills: 0,
+            trees: 0,
+            longest_chain: 0,
+        }
+    }
+}
+
+// Ordnet jeden Tod einer Seite zu; Explosionsopfer der auslösenden Kette
+fn kill_attribution_system(
+    mut death_events: EventReader<DeathEvent>,
+    mut kill_events: EventWriter<KillEvent>,
+    tracker: Res<ChainTracker>,
+    player: Single<Entity, With<Player>>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    for event in death_events.read() {
+        let chain = match (event.kind, event.source) {
+            (DamageKind::Explosion, Some(source)) => {
+                tracker.chain_of(source).map(|c| c.next_link())
+            }
+            _ => None,
+        };
+        // Ohne Kette entscheidet die Quelle selbst
+        let faction = match (chain, event.source) {
+            (Some(chain), _) => Some(chain.instigator),
+            (None, Some(source)) if source == *player => Some(Faction::Player),
+            (None, Some(source)) if enemy_query.contains(source) => Some(Faction::Enemy),
+            _ => None,
+        };
+        kill_events.write(KillEvent {
+            victim: event.target,
+            killer: event.source,
+            faction,
+            kind: event.kind,
+            chain,
+            position: event.position,
+        });
+    }
+}
+
+// Punkte gibt es nur für Gegner, die der Spieler getötet hat
+fn kill_score_system(
+    mut kill_events: EventReader<KillEvent>,
+    mut score_events: EventWriter<ScoreEvent>,
+    player: Single<Entity, With<Player>>,
+) {
+    for event in kill_events.read() {
+        if event.victim == *player || event.faction != Some(Faction::Player) {
+            continue;
+        }
+        score_events.write(ScoreEvent {
+            kind: ScoreKind::Kill,
+            chain: event.chain,
+            position: event.position,
+        });
+    }
+}
+
+fn apply_score_events(mut score_events: EventReader<ScoreEvent>, mut score: ResMut<Score>) {
+    for event in score_events.read() {
+        score.add(event.kind, event.chain);
+    }
+}
+
+// Combo baut sich schrittweise ab, wenn eine Weile nichts passiert
+fn combo_decay_system(time: Res<Time>, mut score: ResMut<Score>) {
+    if score.combo == 0 {
+        return;
+    }
+    score.combo_timer.tick(time.delta());
+    if score.combo_timer.finished() {
+        score.combo -= 1;
+        score.combo_timer.reset();
+    }
+}
+
+fn chain_tracker_cleanup_system(time: Res<Time>, mut tracker: ResMut<ChainTracker>) {
+    let now = time.elapsed_secs();
+    tracker
+        .sources
+        .retain(|_, (_, registered)| now - *registered < CHAIN_MEMORY_SECS);
+}
+
+fn reset_score(mut score: ResMut<Score>, mut tracker: ResMut<ChainTracker>) {
+    *score = Score::default();
+    tracker.sources.clear();
+}
+
+#[derive(Component)]
+struct ScoreText;
+
+fn setup_score_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            ..default()
+        },
+        children![(
+            ScoreText,
+            Node::default(),
+            Text::default(),
+            TextFont {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 32.0,
+                ..default()
+            },
+            TextColor(Color::BLACK)
+        )],
+    ));
+}
+
+fn update_score_text(score: Res<Score>, mut text: Single<&mut Text, With<ScoreText>>) {
+    if !score.is_changed() {
+        return;
+    }
+    text.0 = if score.combo > 0 {
+        format!("Punkte: {}  x{:.2}", score.total, score.multiplier())
+    } else {
+        format!("Punkte: {}", score.total)
+    };
+}
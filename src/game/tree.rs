@@ -3,43 +3,47 @@ use bevy::prelude::*;
 use bevy_common_assets::json::JsonAssetPlugin;
 use bevy_rapier3d::prelude::*;
 use noise::{NoiseFn, Perlin};
-use rand::{Rng, seq::IndexedRandom};
+use rand::Rng;
 use serde::Deserialize;
 
 use crate::AppState;
+use crate::assets::{AssetLoadingState, levels::Level};
 use crate::game::explosion::PendingExplosionSuppressed;
 
-use super::{explosion::PendingExplosion, player::Player};
-
-#[derive(Resource)]
-pub struct RootParticleAssets {
-    pub roots: Vec<Handle<Scene>>,
-    pub splitters: Vec<Handle<Scene>>,
-}
+use super::{
+    camera::CameraShake,
+    explosion::PendingExplosion,
+    level::{CurrentLevel, LevelEntity},
+    particles::{ParticleEvent, ParticlePreset},
+    player::Player,
+};
 
 pub struct TreePlugin;
 
 impl Plugin for TreePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_state(AssetLoadingState::default())
-            .add_plugins(JsonAssetPlugin::<TreeColliderInfo>::new(&[
-                "tree_collider.json",
-            ]))
-            .add_systems(Startup, pre_setup)
-            .add_systems(OnEnter(AssetLoadingState::Done), setup)
-            .add_systems(
-                Update,
-                (
-                    update_tree_colliders,
-                    despawn_root_particles,
-                    animate_particles,
-                )
-                    .run_if(in_state(AppState::Running)),
-            )
-            .add_systems(Update, check_assets_loaded);
+        app.add_plugins(JsonAssetPlugin::<TreeColliderInfo>::new(&[
+            "tree_collider.json",
+        ]))
+        .add_event::<TreeUprootedEvent>()
+        .add_systems(Startup, pre_setup)
+        .add_systems(OnEnter(AssetLoadingState::Done), setup)
+        .add_systems(
+            Update,
+            (update_tree_colliders, uproot_camera_shake_system).run_if(in_state(AppState::Running)),
+        )
+        .add_systems(Update, check_assets_loaded);
     }
 }
 
+// Wird geschickt, sobald ein Baum aus dem Boden gerissen wird
+#[derive(Event, Clone, Debug)]
+pub struct TreeUprootedEvent {
+    pub tree: Entity,
+    pub position: Vec3,
+    pub trunk_radius: f32,
+}
+
 #[derive(Resource, Deserialize, Debug, Clone, bevy::asset::Asset, bevy::reflect::TypePath)]
 struct TreeColliderInfo {
     trunk: ColliderPart,
@@ -86,29 +90,30 @@ fn pre_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     // Trees-Resource mit Collider-Infos laden
     let trees = Trees::new(&asset_server);
     commands.insert_resource(trees.clone());
-    // RootParticleAssets laden
-    let mut roots = Vec::new();
-    let mut splitters = Vec::new();
-    for i in 0..5 {
-        roots.push(asset_server.load(format!("models/roots/root_{i}.glb#Scene0")));
-    }
-    for i in 0..4 {
-        splitters.push(asset_server.load(format!("models/roots/root_splitter_{i}.glb#Scene0")));
-    }
-    commands.insert_resource(RootParticleAssets { roots, splitters });
     // Stake-Sound laden
     let stake_sound = asset_server.load("sounds/stake.wav");
     commands.insert_resource(StakeSound(stake_sound));
 }
 
-fn setup(mut commands: Commands, collider_infos: Res<Assets<TreeColliderInfo>>, trees: Res<Trees>) {
+fn setup(
+    mut commands: Commands,
+    collider_infos: Res<Assets<TreeColliderInfo>>,
+    trees: Res<Trees>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+) {
+    let Some(level) = current_level.get(&levels) else {
+        return;
+    };
+    let forest = &level.forest;
+
     // Bäume platzieren
-    let perlin = Perlin::new(42);
+    let perlin = Perlin::new(forest.seed);
     let mut rng = rand::rng();
 
-    let ground_size = 2000.0;
-    let tree_count = 800;
-    let min_distance = 6.0; // Mindestabstand zwischen Bäumen
+    let ground_size = level.ground.size;
+    let tree_count = forest.tree_count;
+    let min_distance = forest.min_distance; // Mindestabstand zwischen Bäumen
     let mut spawned = 0;
     let mut tries = 0;
     let mut tree_positions: Vec<(f32, f32)> = Vec::with_capacity(tree_count);
@@ -119,9 +124,9 @@ fn setup(mut commands: Commands, collider_infos: Res<Assets<TreeColliderInfo>>,
         let z = rng.random_range(-ground_size / 2.0..ground_size / 2.0);
 
         // Für größere Wäldchen: Noise-Schwelle niedriger und Skalierung kleiner
-        let noise_val = perlin.get([x as f64 / 800.0, z as f64 / 800.0]);
-        let is_wood = noise_val > 0.18;
-        let is_lonely = rng.random_bool(0.01);
+        let noise_val = perlin.get([x as f64 / forest.noise_scale, z as f64 / forest.noise_scale]);
+        let is_wood = noise_val > forest.noise_threshold;
+        let is_lonely = rng.random_bool(forest.lonely_tree_chance);
 
         if is_wood || is_lonely {
             let y_rot = rng.random_range(0.0..std::f32::consts::TAU);
@@ -156,6 +161,7 @@ fn setup(mut commands: Commands, collider_infos: Res<Assets<TreeColliderInfo>>,
                     Visibility::Visible,
                     RigidBody::Fixed,
                     TreeRoot { idx, trunk_radius },
+                    LevelEntity,
                 ));
 
                 tree_positions.push((x, z));
@@ -167,8 +173,8 @@ fn setup(mut commands: Commands, collider_infos: Res<Assets<TreeColliderInfo>>,
 
 #[derive(Debug, Component)]
 pub struct TreeRoot {
-    idx: usize,        // Index im Trees-Array
-    trunk_radius: f32, // Stammbreite
+    idx: usize,            // Index im Trees-Array
+    pub trunk_radius: f32, // Stammbreite
 }
 
 #[derive(Debug, Component)]
@@ -230,23 +236,12 @@ fn update_tree_colliders(
     }
 }
 
-#[derive(Component)]
-struct RootParticle {
-    timer: Timer,
-    velocity: Vec3,
-    rotation_speed: Option<Vec3>, // Nur für Splitter gesetzt
-}
-
-fn despawn_root_particles(
-    mut commands: Commands,
-    time: Res<Time>,
-    mut query: Query<(Entity, &mut RootParticle)>,
+fn uproot_camera_shake_system(
+    mut uproot_events: EventReader<TreeUprootedEvent>,
+    mut shake_events: EventWriter<CameraShake>,
 ) {
-    for (entity, mut particle) in query.iter_mut() {
-        particle.timer.tick(time.delta());
-        if particle.timer.finished() {
-            commands.entity(entity).despawn();
-        }
+    for event in uproot_events.read() {
+        shake_events.write(CameraShake::at(event.position, 0.25, 30.0));
     }
 }
 
@@ -256,14 +251,13 @@ pub fn maybe_uproot_tree(
     commands: &mut Commands,
     tree_entity: Entity,
     tree_query: &Query<(Entity, &Children, &Transform, &TreeRoot), Without<PendingExplosion>>,
-    root_assets: &Res<RootParticleAssets>,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
     stake_sound: &Res<StakeSound>,
-) {
+    particle_events: &mut EventWriter<ParticleEvent>,
+    uproot_events: &mut EventWriter<TreeUprootedEvent>,
+) -> Option<Transform> {
     // Collider-Kinder entfernen
     let Ok((_, children, tree_transform, tree_root)) = tree_query.get(tree_entity) else {
-        return;
+        return None;
     };
 
     for child_entity in children {
@@ -305,138 +299,45 @@ pub fn maybe_uproot_tree(
         PlaybackSettings::ONCE.with_spatial(true),
     ));
 
-    // let max_root_speed = impulse.length(); // Baum-Impuls als Maximum
-    let mut rng = rand::rng();
-    let spread = tree_root.trunk_radius * 1.2;
-
-    // Wurzeln (animiert)
-    for _ in 0..6 {
-        let root_scene = root_assets.roots.choose(&mut rng).unwrap().clone();
-        let angle = rng.random_range(0.0..std::f32::consts::TAU);
-        let offset = Vec3::new(
-            rng.random_range(-spread..spread),
-            -1.2, // weiter unten spawnen!
-            rng.random_range(-spread..spread),
-        );
-        let spawn_pos = tree_transform.translation + offset;
-        let dir = Vec3::new(angle.cos() * 0.45, 0.5, angle.sin() * 0.45).normalize(); // mehr nach außen, weniger nach oben
-        let velocity = dir * rng.random_range(7.0..11.0); // etwas schneller
-
-        // Rotation um die Flugrichtung (vom Baum weg)
-        let axis = dir.normalize();
-        let angle_speed = rng.random_range(-3.0..3.0);
-        let rotation_speed = axis * angle_speed;
-
-        commands.spawn((
-            SceneRoot(root_scene),
-            Transform::from_translation(spawn_pos),
-            Visibility::Visible,
-            RootParticle {
-                timer: Timer::from_seconds(2.0, TimerMode::Once),
-                velocity,
-                rotation_speed: Some(rotation_speed),
-            },
-        ));
-    }
-
-    // Splitter (animiert)
-    for _ in 0..12 {
-        let splitter_scene = root_assets.splitters.choose(&mut rng).unwrap().clone();
-        let angle = rng.random_range(0.0..std::f32::consts::TAU);
-        let offset = Vec3::new(
-            rng.random_range(-spread..spread),
-            0.0,
-            rng.random_range(-spread..spread),
-        );
-        let spawn_pos = tree_transform.translation + offset;
-        let dir = Vec3::new(
-            angle.cos() * 0.7,
-            rng.random_range(0.1..0.4),
-            angle.sin() * 0.7,
-        )
-        .normalize();
-        let velocity = dir * rng.random_range(10.0..16.0);
-        let rotation_speed = Vec3::new(
-            rng.random_range(-6.0..6.0),
-            rng.random_range(-6.0..6.0),
-            rng.random_range(-6.0..6.0),
-        );
-        commands.spawn((
-            SceneRoot(splitter_scene),
-            Transform::from_translation(spawn_pos).with_rotation(Quat::from_euler(
-                EulerRot::XYZ,
-                rng.random_range(0.0..std::f32::consts::TAU),
-                rng.random_range(0.0..std::f32::consts::TAU),
-                rng.random_range(0.0..std::f32::consts::TAU),
-            )),
-            Visibility::Visible,
-            RootParticle {
-                timer: Timer::from_seconds(2.0, TimerMode::Once),
-                velocity,
-                rotation_speed: Some(rotation_speed),
-            },
-        ));
-    }
-
-    // Dreck (animiert, viele, größer, variabel, seitlich)
-    let dirt_color = Color::srgb(0.25, 0.15, 0.07);
-    for _ in 0..160 {
-        let offset = Vec3::new(
-            rng.random_range(-spread..spread),
-            0.0,
-            rng.random_range(-spread..spread),
-        );
-        let spawn_pos = tree_transform.translation + offset;
-        let dirt_dir = Vec3::new(
-            rng.random_range(-1.0..1.0),
-            rng.random_range(0.05..0.25),
-            rng.random_range(-1.0..1.0),
-        )
-        .normalize();
-        let velocity = dirt_dir * rng.random_range(18.0..32.0);
-        let scale = rng.random_range(0.2..0.5); // 10x größer
-
-        commands.spawn((
-            Mesh3d(meshes.add(Mesh::from(bevy::math::primitives::Sphere { radius: 0.08 }))),
-            MeshMaterial3d(materials.add(StandardMaterial {
-                base_color: dirt_color,
-                perceptual_roughness: 1.0,
-                ..default()
-            })),
-            Transform {
-                translation: spawn_pos,
-                scale: Vec3::splat(scale),
-                ..Default::default()
-            },
-            Visibility::Visible,
-            RootParticle {
-                timer: Timer::from_seconds(1.5, TimerMode::Once),
-                velocity,
-                rotation_speed: None,
-            },
-        ));
-    }
-}
+    // Wurzeln, Splitter und Dreck
+    particle_events.write(
+        ParticleEvent::new(ParticlePreset::Uproot, tree_transform.translation)
+            .with_spread(tree_root.trunk_radius * 1.2),
+    );
+    uproot_events.write(TreeUprootedEvent {
+        tree: tree_entity,
+        position: tree_transform.translation,
+        trunk_radius: tree_root.trunk_radius,
+    });
 
-#[derive(States, Debug, Clone, Eq, PartialEq, Hash, Default)]
-enum AssetLoadingState {
-    #[default]
-    Loading,
-    Done,
+    Some(*tree_transform)
 }
 
 fn check_assets_loaded(
     mut next_state: ResMut<NextState<AssetLoadingState>>,
     trees: Option<Res<Trees>>,
     collider_infos: Option<Res<Assets<TreeColliderInfo>>>,
+    current_level: Option<Res<CurrentLevel>>,
+    levels: Res<Assets<Level>>,
+    state: Res<State<AssetLoadingState>>,
     asset_server: Res<AssetServer>,
 ) {
+    if *state.get() == AssetLoadingState::Done {
+        return;
+    }
     let Some(trees) = trees else {
         return;
     };
     let Some(collider_infos) = collider_infos else {
         return;
     };
+    // Ohne geladenes Level gibt es keine Waldparameter
+    let Some(current_level) = current_level else {
+        return;
+    };
+    if current_level.get(&levels).is_none() {
+        return;
+    }
 
     let all_tree_collider_loaded = trees.trees.iter().all(|tree| {
         asset_server.is_loaded(&tree.collider_info)
@@ -447,24 +348,3 @@ fn check_assets_loaded(
         next_state.set(AssetLoadingState::Done);
     }
 }
-
-// Animationssystem für die Partikel
-fn animate_particles(time: Res<Time>, mut query: Query<(&mut Transform, &mut RootParticle)>) {
-    let dt = time.delta_secs();
-    for (mut transform, mut particle) in query.iter_mut() {
-        // Einfache Schwerkraft
-        particle.velocity.y -= 9.81 * dt;
-        transform.translation += particle.velocity * dt;
-
-        // Splitter-Rotation animieren
-        if let Some(rot_speed) = particle.rotation_speed {
-            let delta_rot = Quat::from_euler(
-                EulerRot::XYZ,
-                rot_speed.x * dt,
-                rot_speed.y * dt,
-                rot_speed.z * dt,
-            );
-            transform.rotation = delta_rot * transform.rotation;
-        }
-    }
-}
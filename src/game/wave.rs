@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppState,
+    assets::{
+        AssetLoadingState,
+        enemy_archetypes::EnemyArchetype,
+        levels::{Level, WaveDefinition},
+    },
+};
+
+use super::{
+    asset_cache::GameAssetCache,
+    enemy::{Enemy, EnemyArchetypes, EnemyScaling, spawn_enemy},
+    health::Health,
+    level::{ChangeLevel, CurrentLevel},
+    player::Player,
+};
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveDirector>()
+            .add_systems(Startup, setup_wave_announcement)
+            // Neues Level oder Neustart: wieder bei Welle 1 anfangen
+            .add_systems(OnEnter(AssetLoadingState::Done), reset_wave_director)
+            .add_systems(OnExit(AppState::GameOver), reset_wave_director)
+            .add_systems(
+                Update,
+                (
+                    wave_director_system.run_if(in_state(AssetLoadingState::Done)),
+                    wave_announcement_system,
+                )
+                    .run_if(in_state(AppState::Running)),
+            );
+    }
+}
+
+// Markiert, zu welcher Welle ein Gegner gehört
+#[derive(Component)]
+pub struct WaveMember {
+    pub wave: u32,
+}
+
+pub enum WavePhase {
+    Break(Timer),
+    Active,
+}
+
+#[derive(Resource)]
+pub struct WaveDirector {
+    pub wave: u32, // 1-basiert, 0 = noch keine Welle gestartet
+    pub phase: WavePhase,
+    pub spawned: u32,
+    spawn_delay: Timer,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        WaveDirector {
+            wave: 0,
+            phase: WavePhase::Break(Timer::from_seconds(3.0, TimerMode::Once)),
+            spawned: 0,
+            spawn_delay: Timer::from_seconds(1.0, TimerMode::Once),
+        }
+    }
+}
+
+impl WaveDirector {
+    // Schwierigkeit der aktuellen Welle, Welle 1 ist die Basis
+    pub fn scaling(&self, level: &Level) -> EnemyScaling {
+        let steps = self.wave.saturating_sub(1) as f32;
+        let per_wave = &level.waves.difficulty_per_wave;
+        EnemyScaling {
+            damage: 1.0 + per_wave.damage * steps,
+            max_speed: 1.0 + per_wave.max_speed * steps,
+            shoot_rate: 1.0 + per_wave.shoot_rate * steps,
+            accuracy: 1.0 + per_wave.accuracy * steps,
+        }
+    }
+
+    fn start_next_wave(&mut self) {
+        self.wave += 1;
+        self.phase = WavePhase::Active;
+        self.spawned = 0;
+        self.spawn_delay = Timer::from_seconds(1.0, TimerMode::Once);
+    }
+}
+
+// Definition der Welle (1-basiert); nach den definierten Wellen wächst die letzte weiter
+pub fn wave_definition(level: &Level, wave: u32) -> Option<WaveDefinition> {
+    let definitions = &level.waves.definitions;
+    let last = definitions.last()?;
+    let idx = wave.saturating_sub(1) as usize;
+    Some(definitions.get(idx).cloned().unwrap_or_else(|| {
+        let extra = (idx + 1 - definitions.len()) as u32;
+        WaveDefinition {
+            enemy_count: last.enemy_count + extra * 2,
+            spawn_delay: last.spawn_delay,
+            archetypes: last.archetypes.clone(),
+        }
+    }))
+}
+
+fn reset_wave_director(mut director: ResMut<WaveDirector>) {
+    *director = WaveDirector::default();
+}
+
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn wave_director_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut director: ResMut<WaveDirector>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+    archetypes: Res<EnemyArchetypes>,
+    archetype_assets: Res<Assets<EnemyArchetype>>,
+    mut cache: ResMut<GameAssetCache>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    player_query: Single<&Transform, With<Player>>,
+    enemy_query: Query<(&Health, Option<&WaveMember>), With<Enemy>>,
+    mut change_level: EventWriter<ChangeLevel>,
+    announcement: Single<(&mut Text, &mut WaveAnnouncement)>,
+) {
+    let Some(level) = current_level.get(&levels) else {
+        return;
+    };
+    let (mut text, mut announcement) = announcement.into_inner();
+
+    // --- Pause zwischen den Wellen ---
+    if let WavePhase::Break(timer) = &mut director.phase {
+        timer.tick(time.delta());
+        if timer.finished() {
+            director.start_next_wave();
+            announcement.show(&mut text, format!("Welle {}", director.wave));
+        }
+        return;
+    }
+
+    let wave = director.wave;
+    let Some(definition) = wave_definition(level, wave) else {
+        return;
+    };
+
+    // --- Spawnen, solange das Budget der Welle nicht aufgebraucht ist ---
+    if director.spawned < definition.enemy_count {
+        let alive = enemy_query
+            .iter()
+            .filter(|(health, _)| health.value > 0.0)
+            .count();
+        if alive >= level.enemy_budget.max_alive {
+            return;
+        }
+        director.spawn_delay.tick(time.delta());
+        if !director.spawn_delay.finished() {
+            return;
+        }
+        let Some(archetype) = archetypes.choose(&archetype_assets, &definition.archetypes) else {
+            return;
+        };
+
+        let mut rng = rand::rng();
+        let player_pos = player_query.translation;
+        let angle = rng.random_range(0.0..std::f32::consts::TAU);
+        let dist = rng.random_range(20.0..40.0);
+        // Nicht hinter den Wänden spawnen
+        let bounds = level.bounds() - 2.0;
+        let x = (player_pos.x + dist * angle.cos()).clamp(-bounds, bounds);
+        let z = (player_pos.z + dist * angle.sin()).clamp(-bounds, bounds);
+
+        let entity = spawn_enemy(
+            &mut commands,
+            &mut cache,
+            &mut meshes,
+            &mut materials,
+            archetype,
+            Vec3::new(x, 0.0, z),
+            director.scaling(level),
+        );
+        commands.entity(entity).insert(WaveMember { wave });
+        director.spawned += 1;
+
+        // Nach jedem Spawn neuen zufälligen Delay setzen
+        let delay = rng.random_range(definition.spawn_delay_range());
+        director.spawn_delay = Timer::from_seconds(delay, TimerMode::Once);
+        return;
+    }
+
+    // --- Welle geschafft, sobald keiner ihrer Gegner mehr lebt ---
+    let wave_alive = enemy_query.iter().any(|(health, member)| {
+        health.value > 0.0 && member.is_some_and(|member| member.wave == wave)
+    });
+    if wave_alive {
+        return;
+    }
+
+    let break_timer = Timer::from_seconds(level.waves.break_secs, TimerMode::Once);
+    director.phase = WavePhase::Break(break_timer);
+
+    let last_wave = wave as usize >= level.waves.definitions.len();
+    if last_wave && let Some(next_level) = &level.next_level {
+        announcement.show(&mut text, format!("{} geschafft!", level.name));
+        change_level.write(ChangeLevel {
+            path: next_level.clone(),
+        });
+    } else {
+        announcement.show(&mut text, format!("Welle {wave} geschafft!"));
+    }
+}
+
+#[derive(Component)]
+pub struct WaveAnnouncement {
+    timer: Timer,
+}
+
+impl WaveAnnouncement {
+    fn show(&mut self, text: &mut Text, message: String) {
+        text.0 = message;
+        self.timer.reset();
+    }
+}
+
+fn setup_wave_announcement(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let mut timer = Timer::from_seconds(3.0, TimerMode::Once);
+    timer.tick(timer.duration());
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(15.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            WaveAnnouncement { timer },
+            Node::default(),
+            Text::default(),
+            TextFont {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 56.0,
+                ..default()
+            },
+            TextColor(Color::BLACK)
+        )],
+    ));
+}
+
+fn wave_announcement_system(
+    time: Res<Time>,
+    announcement: Single<(&mut Text, &mut WaveAnnouncement)>,
+) {
+    let (mut text, mut announcement) = announcement.into_inner();
+    if announcement.timer.finished() {
+        return;
+    }
+    announcement.timer.tick(time.delta());
+    if announcement.timer.finished() {
+        text.0.clear();
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::AppState;
+
+use super::{
+    asset_cache::GameAssetCache,
+    bullet::{Bullet, ProjectilePool, spawn_bullet},
+    camera::CameraControl,
+    health::Faction,
+    pickup::PowerUps,
+    player::Player,
+};
+
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup).add_systems(
+            Update,
+            player_shooting_system.run_if(in_state(AppState::Running)),
+        );
+    }
+}
+
+#[derive(Resource, Clone)]
+struct PlayerShootSound(Handle<AudioSource>);
+
+#[derive(Component)]
+pub struct PlayerWeapon {
+    pub cooldown: Timer,
+    pub bullet_speed: f32,
+    pub damage: f32,
+    pub recoil_strength: f32,
+    pub muzzle_offset: f32,
+    pub ammo: u32,
+    pub max_ammo: u32,
+}
+
+impl PlayerWeapon {
+    pub fn new(fire_rate: f32, bullet_speed: f32, recoil_strength: f32) -> Self {
+        // Timer startet abgelaufen, damit der erste Schuss sofort möglich ist
+        let mut cooldown = Timer::from_seconds(1.0 / fire_rate, TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+        PlayerWeapon {
+            cooldown,
+            bullet_speed,
+            damage: 40.0,
+            recoil_strength,
+            muzzle_offset: 1.2,
+            ammo: 100,
+            max_ammo: 200,
+        }
+    }
+}
+
+impl Default for PlayerWeapon {
+    fn default() -> Self {
+        // 4 Schuss pro Sekunde
+        PlayerWeapon::new(4.0, 40.0, 8.0)
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let shoot_sound = asset_server.load("sounds/arrow_shoot.wav");
+    commands.insert_resource(PlayerShootSound(shoot_sound));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn player_shooting_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    camera_control: Res<CameraControl>,
+    player: Single<
+        (
+            Entity,
+            &Transform,
+            &mut ExternalImpulse,
+            &mut PlayerWeapon,
+            &PowerUps,
+        ),
+        With<Player>,
+    >,
+    cache: Res<GameAssetCache>,
+    mut pool: ResMut<ProjectilePool>,
+    shoot_sound: Res<PlayerShootSound>,
+) {
+    let (player_entity, player_transform, mut impulse, mut weapon, power_ups) = player.into_inner();
+    weapon
+        .cooldown
+        .tick(time.delta().mul_f32(power_ups.fire_rate_multiplier()));
+
+    if !mouse.pressed(MouseButton::Left) || !weapon.cooldown.finished() || weapon.ammo == 0 {
+        return;
+    }
+    weapon.cooldown.reset();
+    weapon.ammo -= 1;
+
+    // Schuss entlang der Kamerablickrichtung
+    let aim = camera_control.aim_direction();
+    let spawn_pos = player_transform.translation + aim * weapon.muzzle_offset;
+
+    spawn_bullet(
+        &mut commands,
+        &mut pool,
+        &cache,
+        Bullet::new(player_entity, Faction::Player, weapon.damage),
+        spawn_pos,
+        aim * weapon.bullet_speed,
+    );
+    commands.spawn((
+        AudioPlayer::new(shoot_sound.0.clone()),
+        PlaybackSettings::DESPAWN.with_spatial(true),
+        Transform::from_translation(spawn_pos),
+    ));
+
+    // Rückstoß
+    impulse.impulse -= aim * weapon.recoil_strength;
+}
@@ -1,14 +1,9 @@
-use bevy::math::primitives::*;
 use bevy::{
     audio::{self, AudioPlayer, PlaybackSettings},
     core_pipeline::Skybox,
-    image::{
-        CompressedImageFormats, ImageAddressMode, ImageLoaderSettings, ImageSampler,
-        ImageSamplerDescriptor,
-    },
+    image::CompressedImageFormats,
     prelude::*,
     render::{
-        mesh::VertexAttributeValues,
         render_resource::{TextureViewDescriptor, TextureViewDimension},
         renderer::RenderDevice,
     },
@@ -23,15 +18,31 @@ pub mod game;
 
 use bevy::window::{CursorGrabMode, PrimaryWindow};
 use game::{
+    aiming::AimingPlugin,
+    asset_cache::AssetCachePlugin,
     background_music_plugin::BackgroundMusicPlugin,
     bullet::BulletPlugin,
     camera::CameraPlugin,
     enemy::EnemyPlugin,
+    enemy_ai::EnemyAiPlugin,
+    game_over_gui::GameOverPlugin,
+    ground_mark::GroundMarkPlugin,
+    grounded::GroundedPlugin,
+    particles::ParticlePlugin,
+    level::LevelPlugin,
+    perception::PerceptionPlugin,
+    pickup::PickupPlugin,
+    score::ScorePlugin,
     player::PlayerPlugin,
     skybox_plugin::{SkyboxHandle, SkyboxPlugin},
+    wave::WavePlugin,
+    weapon::WeaponPlugin,
 };
 use game::{enemy::Enemy, skybox_plugin::setup_skybox};
-use game::{gui::GuiPlugin, health::Health};
+use game::{
+    gui::GuiPlugin,
+    health::{Health, HealthPlugin},
+};
 use game::{pause_menu_gui::PauseMenuPlugin, player::Player};
 use rand::Rng;
 use serde::Deserialize;
@@ -44,6 +55,7 @@ pub enum AppState {
     #[default]
     Running,
     Paused,
+    GameOver,
 }
 
 // --- In deiner main() ---
@@ -60,15 +72,29 @@ fn main() {
         ..default()
     }))
     .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+    .add_plugins(AssetCachePlugin)
     .add_plugins(BackgroundMusicPlugin)
+    .add_plugins(HealthPlugin)
+    .add_plugins(GroundedPlugin)
+    .add_plugins(LevelPlugin)
     .add_plugins(SkyboxPlugin)
     .add_plugins(GuiPlugin)
     .add_plugins(BulletPlugin)
     .add_plugins(EnemyPlugin)
+    .add_plugins(PerceptionPlugin)
+    .add_plugins(AimingPlugin)
+    .add_plugins(EnemyAiPlugin)
+    .add_plugins(WavePlugin)
     .add_plugins(CameraPlugin)
     .add_plugins(PlayerPlugin)
+    .add_plugins(WeaponPlugin)
+    .add_plugins(PickupPlugin)
+    .add_plugins(ScorePlugin)
     .add_plugins(PauseMenuPlugin)
+    .add_plugins(GameOverPlugin)
     .add_plugins(ExplosionPlugin)
+    .add_plugins(GroundMarkPlugin)
+    .add_plugins(ParticlePlugin)
     .add_plugins(TreePlugin)
     .add_systems(Startup, setup.after(setup_skybox)) // <--- Reihenfolge explizit!
     .configure_sets(
@@ -95,9 +121,6 @@ fn main() {
 
 fn setup(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
     skybox_handle: Res<SkyboxHandle>,
     mut window: Single<&mut Window, With<PrimaryWindow>>,
 ) {
@@ -118,131 +141,77 @@ fn setup(
             ..default()
         },
     ));
+}
 
-    // Boden (2 km x 2 km)
-    let ground_size = 2000.0;
-    let mud_texture = asset_server.load_with_settings("textures/mud_ground.png", |s: &mut _| {
-        *s = ImageLoaderSettings {
-            sampler: ImageSampler::Descriptor(ImageSamplerDescriptor {
-                address_mode_u: ImageAddressMode::Repeat,
-                address_mode_v: ImageAddressMode::Repeat,
-                ..default()
-            }),
-            ..default()
-        }
-    });
-    let mud_normal =
-        asset_server.load_with_settings("textures/mud_ground_normal.png", |s: &mut _| {
-            *s = ImageLoaderSettings {
-                sampler: ImageSampler::Descriptor(ImageSamplerDescriptor {
-                    address_mode_u: ImageAddressMode::Repeat,
-                    address_mode_v: ImageAddressMode::Repeat,
-                    ..default()
-                }),
-                ..default()
-            }
-        });
-    let mud_gloss =
-        asset_server.load_with_settings("textures/mud_ground_gloss.png", |s: &mut _| {
-            *s = ImageLoaderSettings {
-                sampler: ImageSampler::Descriptor(ImageSamplerDescriptor {
-                    address_mode_u: ImageAddressMode::Repeat,
-                    address_mode_v: ImageAddressMode::Repeat,
-                    ..default()
-                }),
-                ..default()
-            }
-        });
-
-    let mud_material = materials.add(StandardMaterial {
-        base_color_texture: Some(mud_texture),
-        normal_map_texture: Some(mud_normal),
-        metallic_roughness_texture: Some(mud_gloss), // Glossmap als Roughness-Map
-        perceptual_roughness: 0.7,
-        reflectance: 0.05,
-        ..default()
-    });
-
-    let mesh_handle = meshes.add(Mesh::from(Plane3d {
-        normal: Dir3::Y,
-        half_size: Vec2::splat(ground_size / 2.0),
-    }));
-
-    // UVs anpassen für Kachelung
-    if let Some(mesh) = meshes.get_mut(&mesh_handle) {
-        if let Some(VertexAttributeValues::Float32x2(uvs)) =
-            mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0)
-        {
-            for uv in uvs.iter_mut() {
-                uv[0] *= ground_size / 32.0; // 32.0 = sehr oft gekachelt!
-                uv[1] *= ground_size / 32.0;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{diagnostic::DiagnosticsPlugin, gltf::Gltf, state::app::StatesPlugin};
+
+    // Ohne Fenster und Renderer: alle Spiel-Plugins laden und jeden Schedule
+    // initialisieren. Findet z.B. Query-Konflikte (B0001), die sonst erst beim Start auffallen.
+    #[test]
+    fn all_schedules_initialize() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            StatesPlugin,
+            TransformPlugin,
+            DiagnosticsPlugin,
+        ))
+        .init_asset::<AudioSource>()
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<Image>()
+        .init_asset::<Scene>()
+        .init_asset::<Font>()
+        .init_asset::<Gltf>()
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugins((
+            AssetCachePlugin,
+            BackgroundMusicPlugin,
+            HealthPlugin,
+            GroundedPlugin,
+            LevelPlugin,
+            SkyboxPlugin,
+            GuiPlugin,
+            BulletPlugin,
+            EnemyPlugin,
+            PerceptionPlugin,
+            AimingPlugin,
+            EnemyAiPlugin,
+            WavePlugin,
+            CameraPlugin,
+            PlayerPlugin,
+        ))
+        .add_plugins((
+            WeaponPlugin,
+            PickupPlugin,
+            ScorePlugin,
+            PauseMenuPlugin,
+            GameOverPlugin,
+            ExplosionPlugin,
+            GroundMarkPlugin,
+            ParticlePlugin,
+            TreePlugin,
+        ))
+        .insert_state(AppState::default());
+        app.finish();
+        app.cleanup();
+
+        let labels: Vec<_> = app
+            .world()
+            .resource::<Schedules>()
+            .iter()
+            .map(|(_, schedule)| schedule.label())
+            .collect();
+        for label in labels {
+            app.world_mut().schedule_scope(label, |world, schedule| {
+                schedule
+                    .initialize(world)
+                    .expect("Schedule lässt sich nicht aufbauen");
+            });
         }
     }
-
-    commands.spawn((
-        Ground,
-        Mesh3d(mesh_handle),
-        MeshMaterial3d(mud_material),
-        Transform::from_xyz(0.0, -0.05, 0.0),
-        Visibility::Visible,
-        RigidBody::Fixed,
-        Collider::cuboid(ground_size / 2.0, 0.05, ground_size / 2.0),
-        Friction {
-            coefficient: 0.1,
-            combine_rule: CoefficientCombineRule::Average,
-        },
-        ActiveEvents::COLLISION_EVENTS,
-        ActiveCollisionTypes::DYNAMIC_STATIC,
-    ));
-
-    // Wände (jeweils 2 km lang, 10 m hoch, 1 m dick)
-    let wall_color = Color::srgb(0.8, 0.8, 0.8);
-    let wall_material = materials.add(wall_color);
-    spawn_wall(
-        &mut commands,
-        &mut meshes,
-        wall_material.clone(),
-        Vec3::new(2000.0, 10.0, 1.0),
-        Vec3::new(0.0, 5.0, 1000.0),
-    ); // Nord
-    spawn_wall(
-        &mut commands,
-        &mut meshes,
-        wall_material.clone(),
-        Vec3::new(2000.0, 10.0, 1.0),
-        Vec3::new(0.0, 5.0, -1000.0),
-    ); // Süd
-    spawn_wall(
-        &mut commands,
-        &mut meshes,
-        wall_material.clone(),
-        Vec3::new(1.0, 10.0, 2000.0),
-        Vec3::new(1000.0, 5.0, 0.0),
-    ); // Ost
-    spawn_wall(
-        &mut commands,
-        &mut meshes,
-        wall_material.clone(),
-        Vec3::new(1.0, 10.0, 2000.0),
-        Vec3::new(-1000.0, 5.0, 0.0),
-    ); // West
-}
-
-// Walls-Hilfsfunktion (wie gehabt)
-fn spawn_wall(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    material: Handle<StandardMaterial>,
-    size: Vec3,
-    position: Vec3,
-) {
-    commands.spawn((
-        Mesh3d(meshes.add(Cuboid::new(size.x, size.y, size.z))),
-        MeshMaterial3d(material.clone()),
-        Transform::from_translation(position),
-        Visibility::Visible,
-        RigidBody::Fixed,
-        Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
-    ));
 }